@@ -0,0 +1,177 @@
+//! Adaptive sampling switching between one-shot and continuous mode.
+
+use crate::{Clock, ConversionMode, ConversionRate, DynTmp1x2, Error};
+use core::time::Duration;
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Settings for [`AdaptiveSampler`](struct.AdaptiveSampler.html).
+///
+/// Rates of change are in degrees Celsius per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveSamplingSettings {
+    /// Interval between one-shot measurements while the temperature is stable.
+    pub slow_interval: Duration,
+    /// Conversion rate used in continuous mode while the temperature changes.
+    pub fast_rate: ConversionRate,
+    /// Change into continuous mode when the absolute rate of change exceeds
+    /// this value.
+    pub fast_threshold: f32,
+    /// Change back into one-shot mode when the absolute rate of change has
+    /// stayed below this value for `settle_time`.
+    ///
+    /// This should be lower than `fast_threshold`.
+    pub settle_threshold: f32,
+    /// Time the temperature has to be stable before changing back into
+    /// one-shot mode.
+    pub settle_time: Duration,
+}
+
+/// Sampler changing the conversion mode depending on the rate of change of
+/// the temperature.
+///
+/// While the temperature is stable, one-shot measurements are done every
+/// `slow_interval`. When the rate of change between two measurements exceeds
+/// `fast_threshold`, the device is changed into continuous mode with the
+/// `fast_rate` conversion rate. Once the rate of change has stayed below
+/// `settle_threshold` for `settle_time`, the device is changed back into
+/// one-shot mode.
+///
+/// If a mode change fails, the device stays in its current mode and the
+/// measurement is still returned. The change is retried on the next call to
+/// [`read_temperature()`](#method.read_temperature), which returns the error
+/// if it fails again.
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "AdaptiveSampler",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+#[derive(Debug)]
+pub struct AdaptiveSampler<I2C: AsyncI2c, CLK> {
+    dev: DynTmp1x2<I2C>,
+    clock: CLK,
+    settings: AdaptiveSamplingSettings,
+    /// Time and value of the last measurement.
+    last: Option<(Duration, f32)>,
+    /// Time since the rate of change is below the settle threshold.
+    settled_since: Option<Duration>,
+    /// Mode change which could not be done yet.
+    pending_mode: Option<ConversionMode>,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "AdaptiveSampler",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, CLK> AdaptiveSampler<I2C, CLK>
+where
+    I2C: AsyncI2c<Error = E>,
+    CLK: Clock,
+{
+    /// Create a new sampler.
+    ///
+    /// The device can be in either conversion mode.
+    pub fn new(dev: DynTmp1x2<I2C>, clock: CLK, settings: AdaptiveSamplingSettings) -> Self {
+        AdaptiveSampler {
+            dev,
+            clock,
+            settings,
+            last: None,
+            settled_since: None,
+            pending_mode: None,
+        }
+    }
+
+    /// Destroy the sampler, return the device and the clock.
+    pub fn destroy(self) -> (DynTmp1x2<I2C>, CLK) {
+        (self.dev, self.clock)
+    }
+
+    /// Current conversion mode of the device.
+    pub fn mode(&self) -> ConversionMode {
+        self.dev.mode()
+    }
+
+    /// Read the temperature when the next measurement is due.
+    ///
+    /// Returns `nb::Error::WouldBlock` until then. This changes the
+    /// conversion mode as needed.
+    pub async fn read_temperature(&mut self) -> nb::Result<f32, Error<E>> {
+        if let Some(mode) = self.pending_mode {
+            self.change_mode(mode).await?;
+            self.pending_mode = None;
+        }
+        let now = self.clock.now();
+        if !self.is_measurement_due(now) {
+            return Err(nb::Error::WouldBlock);
+        }
+        let temperature = self.dev.read_temperature().await?;
+        let now = self.clock.now();
+        if let Some(mode) = self.update(now, temperature) {
+            if self.change_mode(mode).await.is_err() {
+                self.pending_mode = Some(mode);
+            }
+        }
+        Ok(temperature)
+    }
+
+    fn is_measurement_due(&self, now: Duration) -> bool {
+        let interval = match self.dev.mode() {
+            ConversionMode::OneShot => self.settings.slow_interval,
+            ConversionMode::Continuous => self.settings.fast_rate.sample_period(),
+        };
+        match self.last {
+            None => true,
+            Some((time, _)) => now.saturating_sub(time) >= interval,
+        }
+    }
+
+    /// Store the measurement and return the mode to change into, if any.
+    fn update(&mut self, now: Duration, temperature: f32) -> Option<ConversionMode> {
+        let previous = self.last.replace((now, temperature));
+        let (time, value) = previous?;
+        let elapsed = now.saturating_sub(time).as_secs_f32();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        let rate_of_change = libm::fabsf(temperature - value) / elapsed;
+        match self.dev.mode() {
+            ConversionMode::OneShot if rate_of_change > self.settings.fast_threshold => {
+                Some(ConversionMode::Continuous)
+            }
+            ConversionMode::OneShot => None,
+            ConversionMode::Continuous => {
+                if rate_of_change >= self.settings.settle_threshold {
+                    self.settled_since = None;
+                    return None;
+                }
+                let since = *self.settled_since.get_or_insert(now);
+                if now.saturating_sub(since) >= self.settings.settle_time {
+                    Some(ConversionMode::OneShot)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    async fn change_mode(&mut self, mode: ConversionMode) -> Result<(), Error<E>> {
+        if let Some(mut dev) = self.dev.as_one_shot_mut() {
+            if mode == ConversionMode::Continuous {
+                dev.set_conversion_rate(self.settings.fast_rate).await?;
+            }
+        }
+        self.dev.set_mode(mode).await?;
+        self.settled_since = None;
+        Ok(())
+    }
+}
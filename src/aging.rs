@@ -0,0 +1,202 @@
+//! Thermal aging estimation with the Arrhenius model.
+
+use crate::{Clock, TemperatureSink};
+use core::time::Duration;
+
+/// Boltzmann constant in eV/K.
+const BOLTZMANN_EV_PER_K: f64 = 8.617_333_262e-5;
+/// Offset between degrees Celsius and Kelvin.
+const ZERO_CELSIUS_K: f64 = 273.15;
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// Settings for [`AgingAccumulator`](struct.AgingAccumulator.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AgingSettings {
+    /// Activation energy of the failure mechanism in eV.
+    pub activation_energy: f32,
+    /// Reference temperature in degrees Celsius.
+    pub reference_temperature: f32,
+    /// Rated life at the reference temperature.
+    pub rated_life: Duration,
+}
+
+impl AgingSettings {
+    /// Arrhenius acceleration factor at a temperature in degrees Celsius
+    /// compared to the reference temperature.
+    ///
+    /// This is greater than 1 above and smaller than 1 below the reference
+    /// temperature.
+    pub fn acceleration_factor(&self, temperature: f32) -> f64 {
+        let t = f64::from(temperature) + ZERO_CELSIUS_K;
+        let t_ref = f64::from(self.reference_temperature) + ZERO_CELSIUS_K;
+        let ea = f64::from(self.activation_energy);
+        libm::exp(ea / BOLTZMANN_EV_PER_K * (1.0 / t_ref - 1.0 / t))
+    }
+}
+
+/// Accumulated aging state.
+///
+/// This can be stored with [`to_bytes()`](#method.to_bytes), for example in
+/// non-volatile memory, and restored after a reboot with
+/// [`from_bytes()`](#method.from_bytes) and
+/// [`AgingAccumulator::with_state()`](struct.AgingAccumulator.html#method.with_state).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AgingState {
+    /// Equivalent time at the reference temperature in seconds.
+    pub equivalent_seconds: f64,
+    /// Actual time covered by the readings in seconds.
+    pub operating_seconds: f64,
+}
+
+impl AgingState {
+    /// Size of the serialized state in bytes.
+    pub const SERIALIZED_SIZE: usize = 16;
+
+    /// Serialize the state as little-endian bytes.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut bytes = [0; Self::SERIALIZED_SIZE];
+        bytes[..8].copy_from_slice(&self.equivalent_seconds.to_le_bytes());
+        bytes[8..].copy_from_slice(&self.operating_seconds.to_le_bytes());
+        bytes
+    }
+
+    /// Deserialize a state serialized with [`to_bytes()`](#method.to_bytes).
+    ///
+    /// Returns `None` if the values are negative or not finite, as in
+    /// erased or corrupted memory.
+    pub fn from_bytes(bytes: &[u8; Self::SERIALIZED_SIZE]) -> Option<Self> {
+        let mut value = [0; 8];
+        value.copy_from_slice(&bytes[..8]);
+        let equivalent_seconds = f64::from_le_bytes(value);
+        value.copy_from_slice(&bytes[8..]);
+        let operating_seconds = f64::from_le_bytes(value);
+        let valid = |v: f64| v.is_finite() && v >= 0.0;
+        if valid(equivalent_seconds) && valid(operating_seconds) {
+            Some(AgingState {
+                equivalent_seconds,
+                operating_seconds,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Integrates the Arrhenius acceleration factor over the temperature
+/// history.
+///
+/// The time between two readings, measured with a monotonic
+/// [`Clock`](trait.Clock.html), is weighted with the acceleration factor at
+/// the first of them. The sum is the equivalent time at the reference
+/// temperature, which is compared against the rated life.
+#[derive(Debug)]
+pub struct AgingAccumulator<CLK> {
+    clock: CLK,
+    settings: AgingSettings,
+    state: AgingState,
+    /// Time and acceleration factor of the latest reading.
+    last: Option<(Duration, f64)>,
+}
+
+impl<CLK: Clock> AgingAccumulator<CLK> {
+    /// Create a new accumulator for a new component.
+    pub fn new(clock: CLK, settings: AgingSettings) -> Self {
+        Self::with_state(clock, settings, AgingState::default())
+    }
+
+    /// Create an accumulator continuing from a previously stored state.
+    pub fn with_state(clock: CLK, settings: AgingSettings, state: AgingState) -> Self {
+        AgingAccumulator {
+            clock,
+            settings,
+            state,
+            last: None,
+        }
+    }
+
+    /// Destroy the accumulator, return the clock.
+    pub fn destroy(self) -> CLK {
+        self.clock
+    }
+
+    /// Accumulated state.
+    pub fn state(&self) -> AgingState {
+        self.state
+    }
+
+    /// Equivalent hours at the reference temperature.
+    pub fn equivalent_hours(&self) -> f64 {
+        self.state.equivalent_seconds / SECONDS_PER_HOUR
+    }
+
+    /// Remaining rated life in percent, between 0 and 100.
+    pub fn remaining_life_percent(&self) -> f32 {
+        let rated = self.settings.rated_life.as_secs_f64();
+        if rated <= 0.0 {
+            return 0.0;
+        }
+        let remaining = 100.0 * (1.0 - self.state.equivalent_seconds / rated);
+        remaining.clamp(0.0, 100.0) as f32
+    }
+}
+
+impl<CLK: Clock> TemperatureSink for AgingAccumulator<CLK> {
+    fn add_temperature(&mut self, temperature: f32) {
+        let now = self.clock.now();
+        if let Some((time, factor)) = self.last {
+            let elapsed = now.saturating_sub(time).as_secs_f64();
+            self.state.equivalent_seconds += factor * elapsed;
+            self.state.operating_seconds += elapsed;
+        }
+        self.last = Some((now, self.settings.acceleration_factor(temperature)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    const SETTINGS: AgingSettings = AgingSettings {
+        activation_energy: 0.7,
+        reference_temperature: 55.0,
+        rated_life: Duration::from_secs(1000 * 3600),
+    };
+
+    #[test]
+    fn acceleration_factor_is_one_at_reference() {
+        assert!(f64::abs(SETTINGS.acceleration_factor(55.0) - 1.0) < 1e-12);
+    }
+
+    #[test]
+    fn can_calculate_acceleration_factor() {
+        // exp(0.7 / k * (1 / 328.15 - 1 / 358.15))
+        let factor = SETTINGS.acceleration_factor(85.0);
+        assert!(f64::abs(factor - 7.9528) < 1e-4);
+        assert!(SETTINGS.acceleration_factor(25.0) < 1.0);
+    }
+
+    #[test]
+    fn can_accumulate_aging() {
+        let now = Cell::new(Duration::ZERO);
+        let mut aging = AgingAccumulator::new(|| now.get(), SETTINGS);
+        aging.add_temperature(55.0);
+        now.set(Duration::from_secs(100 * 3600));
+        aging.add_temperature(55.0);
+        assert!(f64::abs(aging.equivalent_hours() - 100.0) < 1e-9);
+        assert!(f32::abs(aging.remaining_life_percent() - 90.0) < 1e-4);
+        assert!(f64::abs(aging.state().operating_seconds - 360_000.0) < 1e-9);
+    }
+
+    #[test]
+    fn state_survives_serialization() {
+        let state = AgingState {
+            equivalent_seconds: 1234.5,
+            operating_seconds: 678.25,
+        };
+        assert_eq!(Some(state), AgingState::from_bytes(&state.to_bytes()));
+        assert_eq!(None, AgingState::from_bytes(&[0xFF; 16]));
+    }
+}
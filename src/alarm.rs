@@ -0,0 +1,351 @@
+//! Latching over-temperature alarms with acknowledgement.
+
+use crate::{marker::mode, Error, FaultQueue, Tmp1x2};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Alarm level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AlarmLevel {
+    /// Warning
+    Warning,
+    /// Critical
+    Critical,
+}
+
+/// Settings of an alarm level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlarmSettings {
+    /// The alarm condition is present when the temperature is at or above
+    /// this value in degrees Celsius.
+    pub threshold: f32,
+    /// The alarm condition is gone when the temperature is below
+    /// `threshold - hysteresis`.
+    pub hysteresis: f32,
+    /// Number of consecutive readings needed to raise or clear the alarm.
+    ///
+    /// This behaves like the fault queue of the device.
+    pub debounce: FaultQueue,
+}
+
+/// Alarm state
+///
+/// Alarms latch until they are acknowledged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AlarmState {
+    /// No alarm (default)
+    #[default]
+    Normal,
+    /// The alarm condition is present and the alarm was not acknowledged.
+    Unacknowledged,
+    /// The alarm condition is present and the alarm was acknowledged.
+    Acknowledged,
+    /// The alarm condition is gone but the alarm was not acknowledged.
+    ReturnedUnacknowledged,
+}
+
+/// Alarm state transition
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AlarmEvent {
+    /// The alarm was raised at the given temperature.
+    Raised {
+        /// Alarm level
+        level: AlarmLevel,
+        /// Temperature in degrees Celsius
+        temperature: f32,
+    },
+    /// The alarm condition was cleared at the given temperature.
+    Cleared {
+        /// Alarm level
+        level: AlarmLevel,
+        /// Temperature in degrees Celsius
+        temperature: f32,
+    },
+    /// The alarm was acknowledged.
+    Acknowledged {
+        /// Alarm level
+        level: AlarmLevel,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Alarm {
+    settings: AlarmSettings,
+    state: AlarmState,
+    /// Debounced alarm condition.
+    condition: bool,
+    /// Consecutive readings contradicting the debounced condition.
+    count: u8,
+}
+
+impl Alarm {
+    fn new(settings: AlarmSettings) -> Self {
+        Alarm {
+            settings,
+            state: AlarmState::Normal,
+            condition: false,
+            count: 0,
+        }
+    }
+
+    /// Returns whether the debounced condition changed.
+    fn update(&mut self, temperature: f32) -> bool {
+        let AlarmSettings {
+            threshold,
+            hysteresis,
+            debounce,
+        } = self.settings;
+        let condition = if self.condition {
+            temperature >= threshold - hysteresis
+        } else {
+            temperature >= threshold
+        };
+        if condition == self.condition {
+            self.count = 0;
+            return false;
+        }
+        self.count += 1;
+        if self.count < debounce.consecutive_faults() {
+            return false;
+        }
+        self.count = 0;
+        self.condition = condition;
+        self.state = match (condition, self.state) {
+            (true, _) => AlarmState::Unacknowledged,
+            (false, AlarmState::Acknowledged) => AlarmState::Normal,
+            (false, _) => AlarmState::ReturnedUnacknowledged,
+        };
+        true
+    }
+}
+
+/// Software alarms with warning and critical levels.
+///
+/// Each level is raised once the temperature has been at or above its
+/// threshold for the number of consecutive readings given by its debounce
+/// setting, and its condition is cleared once the temperature has been below
+/// the threshold minus the hysteresis for as many readings.
+///
+/// Raised alarms latch until they are acknowledged through
+/// [`acknowledge()`](#method.acknowledge): an alarm whose condition is gone
+/// stays in the `ReturnedUnacknowledged` state until then.
+///
+/// All raise, clear and acknowledge transitions are recorded in an event
+/// queue holding up to `N` events. When the queue is full, the oldest event
+/// is discarded.
+#[derive(Debug, Clone)]
+pub struct AlarmManager<const N: usize> {
+    alarms: [Alarm; 2],
+    events: [Option<AlarmEvent>; N],
+    /// Index of the oldest event.
+    head: usize,
+    len: usize,
+    dropped_events: usize,
+}
+
+impl<const N: usize> AlarmManager<N> {
+    /// Create a new alarm manager.
+    pub fn new(warning: AlarmSettings, critical: AlarmSettings) -> Self {
+        AlarmManager {
+            alarms: [Alarm::new(warning), Alarm::new(critical)],
+            events: [None; N],
+            head: 0,
+            len: 0,
+            dropped_events: 0,
+        }
+    }
+
+    /// Current state of an alarm.
+    pub fn state(&self, level: AlarmLevel) -> AlarmState {
+        self.alarms[level as usize].state
+    }
+
+    /// Settings of an alarm.
+    pub fn settings(&self, level: AlarmLevel) -> &AlarmSettings {
+        &self.alarms[level as usize].settings
+    }
+
+    /// Change the settings of an alarm.
+    ///
+    /// The alarm state is kept.
+    pub fn set_settings(&mut self, level: AlarmLevel, settings: AlarmSettings) {
+        self.alarms[level as usize].settings = settings;
+    }
+
+    /// Update the alarms with a new temperature reading.
+    pub fn update_with_temperature(&mut self, temperature: f32) {
+        for level in [AlarmLevel::Warning, AlarmLevel::Critical] {
+            let alarm = &mut self.alarms[level as usize];
+            if alarm.update(temperature) {
+                let event = if alarm.condition {
+                    AlarmEvent::Raised { level, temperature }
+                } else {
+                    AlarmEvent::Cleared { level, temperature }
+                };
+                self.push_event(event);
+            }
+        }
+    }
+
+    /// Acknowledge an alarm.
+    ///
+    /// Returns `false` if the alarm was not waiting for acknowledgement.
+    pub fn acknowledge(&mut self, level: AlarmLevel) -> bool {
+        let alarm = &mut self.alarms[level as usize];
+        alarm.state = match alarm.state {
+            AlarmState::Unacknowledged => AlarmState::Acknowledged,
+            AlarmState::ReturnedUnacknowledged => AlarmState::Normal,
+            AlarmState::Normal | AlarmState::Acknowledged => return false,
+        };
+        self.push_event(AlarmEvent::Acknowledged { level });
+        true
+    }
+
+    /// Take the oldest event from the queue.
+    pub fn pop_event(&mut self) -> Option<AlarmEvent> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        event
+    }
+
+    /// Number of events discarded because the queue was full.
+    pub fn dropped_events(&self) -> usize {
+        self.dropped_events
+    }
+
+    fn push_event(&mut self, event: AlarmEvent) {
+        if N == 0 {
+            self.dropped_events += 1;
+            return;
+        }
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+            self.dropped_events += 1;
+        }
+        self.events[(self.head + self.len) % N] = Some(event);
+        self.len += 1;
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "AlarmManager",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<const N: usize> AlarmManager<N> {
+    /// Read the temperature and update the alarms.
+    ///
+    /// Returns the temperature read. On error the alarms are not changed.
+    pub async fn update<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<f32, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        let temperature = dev.read_temperature().await?;
+        self.update_with_temperature(temperature);
+        Ok(temperature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WARNING: AlarmSettings = AlarmSettings {
+        threshold: 40.0,
+        hysteresis: 2.0,
+        debounce: FaultQueue::_4,
+    };
+    const CRITICAL: AlarmSettings = AlarmSettings {
+        threshold: 60.0,
+        hysteresis: 2.0,
+        debounce: FaultQueue::_1,
+    };
+
+    fn raised(level: AlarmLevel, temperature: f32) -> Option<AlarmEvent> {
+        Some(AlarmEvent::Raised { level, temperature })
+    }
+
+    fn cleared(level: AlarmLevel, temperature: f32) -> Option<AlarmEvent> {
+        Some(AlarmEvent::Cleared { level, temperature })
+    }
+
+    #[test]
+    fn alarm_is_debounced() {
+        let mut alarms = AlarmManager::<4>::new(WARNING, CRITICAL);
+        alarms.update_with_temperature(41.0);
+        alarms.update_with_temperature(39.0);
+        alarms.update_with_temperature(41.0);
+        alarms.update_with_temperature(42.0);
+        alarms.update_with_temperature(43.0);
+        assert_eq!(AlarmState::Normal, alarms.state(AlarmLevel::Warning));
+        alarms.update_with_temperature(44.0);
+        assert_eq!(
+            AlarmState::Unacknowledged,
+            alarms.state(AlarmLevel::Warning)
+        );
+        assert_eq!(raised(AlarmLevel::Warning, 44.0), alarms.pop_event());
+        assert_eq!(None, alarms.pop_event());
+    }
+
+    #[test]
+    fn alarm_clears_below_hysteresis() {
+        let mut alarms = AlarmManager::<4>::new(WARNING, CRITICAL);
+        alarms.update_with_temperature(61.0);
+        assert_eq!(raised(AlarmLevel::Critical, 61.0), alarms.pop_event());
+        alarms.update_with_temperature(58.5);
+        assert_eq!(None, alarms.pop_event());
+        alarms.update_with_temperature(57.5);
+        assert_eq!(cleared(AlarmLevel::Critical, 57.5), alarms.pop_event());
+        assert_eq!(
+            AlarmState::ReturnedUnacknowledged,
+            alarms.state(AlarmLevel::Critical)
+        );
+    }
+
+    #[test]
+    fn acknowledged_alarm_returns_to_normal_when_cleared() {
+        let mut alarms = AlarmManager::<4>::new(WARNING, CRITICAL);
+        alarms.update_with_temperature(61.0);
+        assert!(alarms.acknowledge(AlarmLevel::Critical));
+        assert!(!alarms.acknowledge(AlarmLevel::Critical));
+        assert_eq!(AlarmState::Acknowledged, alarms.state(AlarmLevel::Critical));
+        alarms.update_with_temperature(30.0);
+        assert_eq!(AlarmState::Normal, alarms.state(AlarmLevel::Critical));
+        assert_eq!(raised(AlarmLevel::Critical, 61.0), alarms.pop_event());
+        assert_eq!(
+            Some(AlarmEvent::Acknowledged {
+                level: AlarmLevel::Critical
+            }),
+            alarms.pop_event()
+        );
+        assert_eq!(cleared(AlarmLevel::Critical, 30.0), alarms.pop_event());
+    }
+
+    #[test]
+    fn full_queue_discards_oldest_event() {
+        let mut alarms = AlarmManager::<2>::new(WARNING, CRITICAL);
+        alarms.update_with_temperature(61.0);
+        alarms.update_with_temperature(30.0);
+        alarms.update_with_temperature(61.0);
+        assert_eq!(1, alarms.dropped_events());
+        assert_eq!(cleared(AlarmLevel::Critical, 30.0), alarms.pop_event());
+        assert_eq!(raised(AlarmLevel::Critical, 61.0), alarms.pop_event());
+        assert_eq!(None, alarms.pop_event());
+    }
+}
@@ -0,0 +1,290 @@
+//! Driver which borrows the I²C bus on each use.
+
+use crate::{
+    marker::{mode, range},
+    Config, ModeChangeError, Register, SlaveAddr, Tmp1x2, DEVICE_BASE_ADDRESS,
+};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Result of a mode or range change of the owning driver.
+type TransitionResult<I2C, E, MODE, RANGE, M, R> =
+    Result<Tmp1x2<I2C, M, R>, ModeChangeError<E, Tmp1x2<I2C, MODE, RANGE>>>;
+
+/// TMP1X2 device driver which does not own the I²C bus.
+///
+/// This only stores the device address and the cached driver state.
+/// The bus is passed in through [`bind()`](#method.bind), which gives access
+/// to the complete [`Tmp1x2`](struct.Tmp1x2.html) API for as long as the
+/// returned [`BoundTmp1x2`](struct.BoundTmp1x2.html) lives. The driver state,
+/// including the one-shot measurement state, is stored back when it is
+/// dropped.
+#[derive(Debug)]
+pub struct BorrowingTmp1x2<MODE, RANGE = range::Dynamic> {
+    address: u8,
+    config: Config,
+    config_is_known: bool,
+    pointer: Option<Register>,
+    a_temperature_conversion_was_started: bool,
+    _mode: PhantomData<MODE>,
+    _range: PhantomData<RANGE>,
+}
+
+impl BorrowingTmp1x2<mode::Continuous> {
+    /// Create new instance of the TMP102 or TMP112x device.
+    ///
+    /// By default they are in continuous conversion mode.
+    pub fn new(address: SlaveAddr) -> Self {
+        BorrowingTmp1x2 {
+            address: address.addr(DEVICE_BASE_ADDRESS),
+            config: Config::default(),
+            config_is_known: true,
+            pointer: None,
+            a_temperature_conversion_was_started: false,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
+    }
+}
+
+impl<MODE, RANGE> BorrowingTmp1x2<MODE, RANGE> {
+    /// Copy the driver state for another conversion mode.
+    ///
+    /// Like a mode change of the owning driver, this forgets a started
+    /// one-shot measurement.
+    pub(crate) fn with_mode<M>(&self) -> BorrowingTmp1x2<M, RANGE> {
+        BorrowingTmp1x2 {
+            address: self.address,
+            config: self.config.clone(),
+            config_is_known: self.config_is_known,
+            pointer: self.pointer,
+            a_temperature_conversion_was_started: false,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
+    }
+
+    /// Stop tracking the measurement range in the driver type.
+    ///
+    /// This does not alter the device configuration.
+    pub fn into_dynamic_range(self) -> BorrowingTmp1x2<MODE, range::Dynamic> {
+        BorrowingTmp1x2 {
+            address: self.address,
+            config: self.config,
+            config_is_known: self.config_is_known,
+            pointer: self.pointer,
+            a_temperature_conversion_was_started: self.a_temperature_conversion_was_started,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BorrowingTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<MODE, RANGE> BorrowingTmp1x2<MODE, RANGE> {
+    /// Use the driver with the given I²C bus.
+    ///
+    /// The returned value dereferences to a [`Tmp1x2`](struct.Tmp1x2.html)
+    /// driver. The bus is only borrowed until it is dropped.
+    pub fn bind<'a, I2C: AsyncI2c>(
+        &'a mut self,
+        i2c: &'a mut I2C,
+    ) -> BoundTmp1x2<'a, I2C, MODE, RANGE> {
+        let dev = self.with_bus(i2c);
+        BoundTmp1x2 { state: self, dev }
+    }
+
+    fn with_bus<'a, I2C: AsyncI2c>(&self, i2c: &'a mut I2C) -> Tmp1x2<&'a mut I2C, MODE, RANGE> {
+        Tmp1x2 {
+            i2c,
+            address: self.address,
+            config: self.config.clone(),
+            config_is_known: self.config_is_known,
+            pointer: self.pointer,
+            a_temperature_conversion_was_started: self.a_temperature_conversion_was_started,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
+    }
+
+    fn store<I2C: AsyncI2c>(&mut self, dev: &Tmp1x2<I2C, MODE, RANGE>) {
+        self.config = dev.config.clone();
+        self.config_is_known = dev.config_is_known;
+        self.pointer = dev.pointer;
+        self.a_temperature_conversion_was_started = dev.a_temperature_conversion_was_started;
+    }
+
+    pub(crate) fn from_driver<I2C: AsyncI2c>(dev: &Tmp1x2<I2C, MODE, RANGE>) -> Self {
+        BorrowingTmp1x2 {
+            address: dev.address,
+            config: dev.config.clone(),
+            config_is_known: dev.config_is_known,
+            pointer: dev.pointer,
+            a_temperature_conversion_was_started: dev.a_temperature_conversion_was_started,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
+    }
+
+    fn from_transition<I2C, E, M, R>(
+        result: TransitionResult<I2C, E, MODE, RANGE, M, R>,
+    ) -> Result<BorrowingTmp1x2<M, R>, ModeChangeError<E, Self>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        match result {
+            Ok(dev) => Ok(BorrowingTmp1x2::from_driver(&dev)),
+            Err(ModeChangeError::I2C(e, dev)) => {
+                Err(ModeChangeError::I2C(e, BorrowingTmp1x2::from_driver(&dev)))
+            }
+        }
+    }
+
+    /// Change into normal measurement range.
+    ///
+    /// See [`Tmp1x2::into_normal()`](struct.Tmp1x2.html#method.into_normal).
+    pub async fn into_normal<I2C, E>(
+        self,
+        i2c: &mut I2C,
+    ) -> Result<BorrowingTmp1x2<MODE, range::Normal>, ModeChangeError<E, Self>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        Self::from_transition(self.with_bus(i2c).into_normal().await)
+    }
+
+    /// Change into extended measurement range.
+    ///
+    /// See [`Tmp1x2::into_extended()`](struct.Tmp1x2.html#method.into_extended).
+    pub async fn into_extended<I2C, E>(
+        self,
+        i2c: &mut I2C,
+    ) -> Result<BorrowingTmp1x2<MODE, range::Extended>, ModeChangeError<E, Self>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        Self::from_transition(self.with_bus(i2c).into_extended().await)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BorrowingTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<RANGE> BorrowingTmp1x2<mode::Continuous, RANGE> {
+    /// Change into one-shot conversion mode (shutdown).
+    ///
+    /// If the mode change failed you will get a `ModeChangeError`.
+    /// You can get the unchanged device back from it.
+    pub async fn into_one_shot<I2C, E>(
+        self,
+        i2c: &mut I2C,
+    ) -> Result<BorrowingTmp1x2<mode::OneShot, RANGE>, ModeChangeError<E, Self>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        Self::from_transition(self.with_bus(i2c).into_one_shot().await)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BorrowingTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<RANGE> BorrowingTmp1x2<mode::OneShot, RANGE> {
+    /// Change into continuous conversion mode.
+    ///
+    /// If the mode change failed you will get a `ModeChangeError`.
+    /// You can get the unchanged device back from it.
+    pub async fn into_continuous<I2C, E>(
+        self,
+        i2c: &mut I2C,
+    ) -> Result<BorrowingTmp1x2<mode::Continuous, RANGE>, ModeChangeError<E, Self>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        Self::from_transition(self.with_bus(i2c).into_continuous().await)
+    }
+}
+
+/// [`BorrowingTmp1x2`](struct.BorrowingTmp1x2.html) driver bound to an I²C
+/// bus.
+///
+/// This dereferences to a [`Tmp1x2`](struct.Tmp1x2.html) driver. The driver
+/// state is stored back into the `BorrowingTmp1x2` when this is dropped.
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BoundTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+#[derive(Debug)]
+pub struct BoundTmp1x2<'a, I2C: AsyncI2c, MODE, RANGE> {
+    state: &'a mut BorrowingTmp1x2<MODE, RANGE>,
+    dev: Tmp1x2<&'a mut I2C, MODE, RANGE>,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BoundTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<'a, I2C: AsyncI2c, MODE, RANGE> Deref for BoundTmp1x2<'a, I2C, MODE, RANGE> {
+    type Target = Tmp1x2<&'a mut I2C, MODE, RANGE>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.dev
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BoundTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<'a, I2C: AsyncI2c, MODE, RANGE> DerefMut for BoundTmp1x2<'a, I2C, MODE, RANGE> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.dev
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "BoundTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<'a, I2C: AsyncI2c, MODE, RANGE> Drop for BoundTmp1x2<'a, I2C, MODE, RANGE> {
+    fn drop(&mut self) {
+        self.state.store(&self.dev);
+    }
+}
@@ -0,0 +1,99 @@
+//! Averaging of several one-shot measurements.
+
+use crate::{marker::mode, stats::RunningStats, BurstError, ConversionRate, Tmp1x2};
+use core::num::NonZeroU8;
+#[cfg(not(feature = "async"))]
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+#[cfg(feature = "async")]
+use embedded_hal_async::{delay::DelayNs, i2c::I2c as AsyncI2c};
+
+/// Statistics of a burst of one-shot measurements.
+///
+/// See [`read_temperature_burst()`](struct.Tmp1x2.html#method.read_temperature_burst).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BurstStatistics {
+    /// Number of measurements.
+    pub count: u8,
+    /// Mean temperature in degrees Celsius.
+    pub mean: f32,
+    /// Sample standard deviation in degrees Celsius.
+    ///
+    /// This is 0 for a single measurement.
+    pub standard_deviation: f32,
+    /// Lowest temperature in degrees Celsius.
+    pub min: f32,
+    /// Highest temperature in degrees Celsius.
+    pub max: f32,
+}
+
+/// Delay between checks whether a conversion is finished after the maximum
+/// conversion time has passed.
+const POLL_INTERVAL_MS: u32 = 1;
+
+/// A measurement times out after this many maximum conversion times.
+const TIMEOUT_CONVERSIONS: u32 = 3;
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::OneShot, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Perform `count` one-shot measurements back to back and return their
+    /// statistics.
+    ///
+    /// Each measurement is triggered once the previous one is finished.
+    /// The device returns to the shutdown state after the last one.
+    /// A measurement started through [`read_temperature()`](#method.read_temperature)
+    /// which is still in progress is completed and counted as the first one.
+    ///
+    /// Returns `BurstError::Timeout` if a measurement is not finished within
+    /// three times the maximum conversion time, for example because the
+    /// device does not respond to the one-shot trigger.
+    pub async fn read_temperature_burst<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        count: NonZeroU8,
+    ) -> Result<BurstStatistics, BurstError<E>> {
+        let conversion_time = ConversionRate::default().max_conversion_time();
+        let conversion_time_ms = conversion_time.as_millis() as u32;
+        let max_polls = (TIMEOUT_CONVERSIONS - 1) * conversion_time_ms / POLL_INTERVAL_MS;
+        let mut stats = RunningStats::new();
+        while stats.count() < u32::from(count.get()) {
+            let mut polls = 0;
+            let temperature = loop {
+                match self.read_temperature().await {
+                    Ok(temperature) => break temperature,
+                    Err(nb::Error::WouldBlock) if polls > max_polls => {
+                        return Err(BurstError::Timeout)
+                    }
+                    Err(nb::Error::WouldBlock) => {
+                        let wait_ms = if polls == 0 {
+                            conversion_time_ms
+                        } else {
+                            POLL_INTERVAL_MS
+                        };
+                        delay.delay_ms(wait_ms).await;
+                        polls += 1;
+                    }
+                    Err(nb::Error::Other(e)) => return Err(e.into()),
+                }
+            };
+            stats.add(temperature);
+        }
+        Ok(BurstStatistics {
+            count: count.get(),
+            mean: stats.mean(),
+            standard_deviation: stats.standard_deviation(),
+            min: stats.min(),
+            max: stats.max(),
+        })
+    }
+}
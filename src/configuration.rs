@@ -1,8 +1,13 @@
-use crate::conversion::{convert_temp_to_register_extended, convert_temp_to_register_normal};
+use crate::conversion::{
+    convert_temp_from_register_in_mode, convert_temp_to_register_extended,
+    convert_temp_to_register_normal,
+};
 use crate::RegisterU16;
 use crate::{
-    marker::mode, AlertPolarity, BitFlagsHigh as BFH, BitFlagsLow as BFL, Config,
-    ConversionRate as CR, Error, FaultQueue, ModeChangeError, Register, ThermostatMode, Tmp1x2,
+    marker::{mode, range},
+    AlertPolarity, BitFlagsHigh as BFH, BitFlagsLow as BFL, Config, ConfigRegister, Configuration,
+    ConfigurationDiff, ConversionRate as CR, Error, FaultQueue, Mismatch, ModeChangeError,
+    Register, ThermostatError, ThermostatMode, ThermostatOutput, Tmp1x2, TransactionError,
 };
 use core::marker::PhantomData;
 #[cfg(not(feature = "async"))]
@@ -18,7 +23,7 @@ use embedded_hal_async::i2c::I2c as AsyncI2c;
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E> Tmp1x2<I2C, mode::Continuous>
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::Continuous, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
@@ -28,17 +33,75 @@ where
     /// You can get the unchanged device back from it.
     pub async fn into_one_shot(
         mut self,
-    ) -> Result<Tmp1x2<I2C, mode::OneShot>, ModeChangeError<E, Self>> {
+    ) -> Result<Tmp1x2<I2C, mode::OneShot, RANGE>, ModeChangeError<E, Self>> {
         if let Err(Error::I2C(e)) = self.config_one_shot().await {
             return Err(ModeChangeError::I2C(e, self));
         }
-        Ok(Tmp1x2 {
-            i2c: self.i2c,
-            address: self.address,
-            config: self.config,
-            a_temperature_conversion_was_started: false,
-            _mode: PhantomData,
-        })
+        Ok(self.with_mode())
+    }
+
+    /// Configure the device as a standalone thermostat driving the alert pin.
+    ///
+    /// The alert pin is set to `active_level` while the output should be on:
+    /// - A cooler is turned on when the temperature reaches `setpoint` and
+    ///   turned off when it falls below `setpoint - hysteresis`.
+    /// - A heater is turned on when the temperature falls below `setpoint`
+    ///   and turned off when it reaches `setpoint + hysteresis`.
+    ///
+    /// The device is set to comparator mode and the alert polarity is derived
+    /// from the output and `active_level`. The fault queue sets the number of
+    /// consecutive measurements needed to switch, which improves noise
+    /// immunity. The conversion rate and measurement range are unchanged.
+    ///
+    /// Returns `ThermostatError::InvalidThresholds` if the hysteresis is not
+    /// positive or if the resulting thresholds are not representable as
+    /// distinct values in the current measurement range.
+    pub async fn configure_as_thermostat(
+        &mut self,
+        setpoint: f32,
+        hysteresis: f32,
+        output: ThermostatOutput,
+        active_level: AlertPolarity,
+        fault_queue: FaultQueue,
+    ) -> Result<(), ThermostatError<E>> {
+        if hysteresis.is_nan() || hysteresis <= 0.0 {
+            return Err(ThermostatError::InvalidThresholds);
+        }
+        let current = ConfigRegister::from(self.current_config().await?);
+        let extended_mode = current.is_extended_mode();
+        // The alert is active while the temperature is high, so a heater
+        // is driven with the inverse polarity.
+        let (low, high, alert_polarity) = match (output, active_level) {
+            (ThermostatOutput::Cooler, polarity) => (setpoint - hysteresis, setpoint, polarity),
+            (ThermostatOutput::Heater, AlertPolarity::ActiveLow) => {
+                (setpoint, setpoint + hysteresis, AlertPolarity::ActiveHigh)
+            }
+            (ThermostatOutput::Heater, AlertPolarity::ActiveHigh) => {
+                (setpoint, setpoint + hysteresis, AlertPolarity::ActiveLow)
+            }
+        };
+        let (min, max) = if extended_mode {
+            (-256.0, 255.875)
+        } else {
+            (-128.0, 127.9375)
+        };
+        let t_low = u16::from(encode_threshold(low, extended_mode)) as i16;
+        let t_high = u16::from(encode_threshold(high, extended_mode)) as i16;
+        if low < min || high > max || t_low >= t_high {
+            return Err(ThermostatError::InvalidThresholds);
+        }
+        let configuration = Configuration {
+            extended_mode,
+            conversion_rate: current.conversion_rate(),
+            fault_queue,
+            alert_polarity,
+            thermostat_mode: ThermostatMode::Comparator,
+            low_temperature_threshold: low,
+            high_temperature_threshold: high,
+        };
+        let registers = self.encode_configuration(&configuration);
+        self.write_configuration_registers(registers).await?;
+        Ok(())
     }
 }
 
@@ -50,7 +113,7 @@ where
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E> Tmp1x2<I2C, mode::OneShot>
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::OneShot, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
@@ -60,22 +123,17 @@ where
     /// You can get the unchanged device back from it.
     pub async fn into_continuous(
         mut self,
-    ) -> Result<Tmp1x2<I2C, mode::Continuous>, ModeChangeError<E, Self>> {
+    ) -> Result<Tmp1x2<I2C, mode::Continuous, RANGE>, ModeChangeError<E, Self>> {
         if let Err(Error::I2C(e)) = self.config_continuous().await {
             return Err(ModeChangeError::I2C(e, self));
         }
-        Ok(Tmp1x2 {
-            i2c: self.i2c,
-            address: self.address,
-            config: self.config,
-            a_temperature_conversion_was_started: false,
-            _mode: PhantomData,
-        })
+        Ok(self.with_mode())
     }
 
     pub(crate) async fn trigger_one_shot_measurement(&mut self) -> Result<(), Error<E>> {
+        let config = self.current_config().await?;
         // This bit is not stored
-        self.write_register(Register::CONFIG, self.config.with_high_msb(BFH::ONE_SHOT))
+        self.write_register(Register::Config, config.with_high_msb(BFH::ONE_SHOT))
             .await
     }
 }
@@ -88,40 +146,51 @@ where
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E, MODE> Tmp1x2<I2C, MODE>
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
-    async fn config_continuous(&mut self) -> Result<(), Error<E>> {
-        self.write_config(self.config.with_low_msb(BFH::SHUTDOWN))
-            .await
+    /// Change the driver type without changing the device configuration.
+    pub(crate) fn with_mode<M>(self) -> Tmp1x2<I2C, M, RANGE> {
+        Tmp1x2 {
+            i2c: self.i2c,
+            address: self.address,
+            config: self.config,
+            config_is_known: self.config_is_known,
+            pointer: self.pointer,
+            a_temperature_conversion_was_started: false,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
     }
 
-    async fn config_one_shot(&mut self) -> Result<(), Error<E>> {
-        self.write_config(self.config.with_high_msb(BFH::SHUTDOWN))
-            .await
+    /// Change the driver type without changing the device configuration.
+    pub(crate) fn with_range<R>(self) -> Tmp1x2<I2C, MODE, R> {
+        Tmp1x2 {
+            i2c: self.i2c,
+            address: self.address,
+            config: self.config,
+            config_is_known: self.config_is_known,
+            pointer: self.pointer,
+            a_temperature_conversion_was_started: self.a_temperature_conversion_was_started,
+            _mode: PhantomData,
+            _range: PhantomData,
+        }
     }
 
-    /// Enable the extended measurement mode.
-    ///
-    /// This allows measurement of temperatures above 128°C.
-    pub async fn enable_extended_mode(&mut self) -> Result<(), Error<E>> {
-        self.write_config(self.config.with_high_lsb(BFL::EXTENDED_MODE))
-            .await
+    pub(crate) async fn config_continuous(&mut self) -> Result<(), Error<E>> {
+        let config = self.current_config().await?;
+        self.write_config(config.with_low_msb(BFH::SHUTDOWN)).await
     }
 
-    /// Disable the extended measurement mode.
-    ///
-    /// This puts the device in normal measurement mode. It will not measure
-    /// temperatures above 128°C.
-    pub async fn disable_extended_mode(&mut self) -> Result<(), Error<E>> {
-        self.write_config(self.config.with_low_lsb(BFL::EXTENDED_MODE))
-            .await
+    pub(crate) async fn config_one_shot(&mut self) -> Result<(), Error<E>> {
+        let config = self.current_config().await?;
+        self.write_config(config.with_high_msb(BFH::SHUTDOWN)).await
     }
 
     /// Set the conversion rate when in continuous conversion mode.
     pub async fn set_conversion_rate(&mut self, rate: CR) -> Result<(), Error<E>> {
-        let Config { lsb, msb } = self.config;
+        let Config { lsb, msb } = self.current_config().await?;
         match rate {
             CR::_0_25Hz => {
                 self.write_config(Config {
@@ -139,7 +208,7 @@ where
             }
             CR::_4Hz => {
                 self.write_config(Config {
-                    lsb: lsb | BFL::CONV_RATE1 & !BFL::CONV_RATE0,
+                    lsb: (lsb & !BFL::CONV_RATE0) | BFL::CONV_RATE1,
                     msb,
                 })
                 .await
@@ -154,53 +223,32 @@ where
         }
     }
 
-    /// Set the high temperature threshold.
-    ///
-    /// The value provided will be capped to be in the interval
-    /// `[-128.0, 127.9375]` in normal mode and `[-256.0, 255.875]` in
-    /// extended mode.
-    pub async fn set_high_temperature_threshold(
-        &mut self,
-        temperature: f32,
-    ) -> Result<(), Error<E>> {
-        self.set_temperature_threshold(temperature, Register::T_HIGH)
-            .await
+    pub(crate) async fn write_extended_mode(&mut self, enable: bool) -> Result<(), Error<E>> {
+        let config = self.current_config().await?;
+        if enable {
+            self.write_config(config.with_high_lsb(BFL::EXTENDED_MODE))
+                .await
+        } else {
+            self.write_config(config.with_low_lsb(BFL::EXTENDED_MODE))
+                .await
+        }
     }
 
-    /// Set the low temperature threshold.
-    ///
-    /// The value provided will be capped to be in the interval
-    /// `[-128.0, 127.9375]` in normal mode and `[-256.0, 255.875]` in
-    /// extended mode.
-    pub async fn set_low_temperature_threshold(
+    pub(crate) async fn set_temperature_threshold(
         &mut self,
         temperature: f32,
+        register: Register,
     ) -> Result<(), Error<E>> {
-        self.set_temperature_threshold(temperature, Register::T_LOW)
+        let extended_mode = (self.current_config().await?.lsb & BFL::EXTENDED_MODE) != 0;
+        self.write_register(register, encode_threshold(temperature, extended_mode))
             .await
     }
 
-    async fn set_temperature_threshold(
-        &mut self,
-        temperature: f32,
-        register: u8,
-    ) -> Result<(), Error<E>> {
-        if (self.config.lsb & BFL::EXTENDED_MODE) != 0 {
-            let (msb, lsb) = convert_temp_to_register_extended(temperature);
-            self.write_register(register, RegisterU16 { lsb, msb })
-                .await
-        } else {
-            let (msb, lsb) = convert_temp_to_register_normal(temperature);
-            self.write_register(register, RegisterU16 { lsb, msb })
-                .await
-        }
-    }
-
     /// Set the fault queue.
     ///
     /// Set the number of consecutive faults that will trigger an alert.
     pub async fn set_fault_queue(&mut self, fq: FaultQueue) -> Result<(), Error<E>> {
-        let Config { lsb, msb } = self.config;
+        let Config { lsb, msb } = self.current_config().await?;
         match fq {
             FaultQueue::_1 => {
                 self.write_config(Config {
@@ -219,7 +267,7 @@ where
             FaultQueue::_4 => {
                 self.write_config(Config {
                     lsb,
-                    msb: msb | BFH::FAULT_QUEUE1 & !BFH::FAULT_QUEUE0,
+                    msb: (msb & !BFH::FAULT_QUEUE0) | BFH::FAULT_QUEUE1,
                 })
                 .await
             }
@@ -235,13 +283,14 @@ where
 
     /// Set the alert polarity.
     pub async fn set_alert_polarity(&mut self, polarity: AlertPolarity) -> Result<(), Error<E>> {
+        let config = self.current_config().await?;
         match polarity {
             AlertPolarity::ActiveLow => {
-                self.write_config(self.config.with_low_msb(BFH::ALERT_POLARITY))
+                self.write_config(config.with_low_msb(BFH::ALERT_POLARITY))
                     .await
             }
             AlertPolarity::ActiveHigh => {
-                self.write_config(self.config.with_high_msb(BFH::ALERT_POLARITY))
+                self.write_config(config.with_high_msb(BFH::ALERT_POLARITY))
                     .await
             }
         }
@@ -249,13 +298,14 @@ where
 
     /// Set the thermostat mode.
     pub async fn set_thermostat_mode(&mut self, mode: ThermostatMode) -> Result<(), Error<E>> {
+        let config = self.current_config().await?;
         match mode {
             ThermostatMode::Comparator => {
-                self.write_config(self.config.with_low_msb(BFH::THERMOSTAT))
+                self.write_config(config.with_low_msb(BFH::THERMOSTAT))
                     .await
             }
             ThermostatMode::Interrupt => {
-                self.write_config(self.config.with_high_msb(BFH::THERMOSTAT))
+                self.write_config(config.with_high_msb(BFH::THERMOSTAT))
                     .await
             }
         }
@@ -266,7 +316,8 @@ where
     /// *Note:* This does not alter the state or configuration of the device.
     ///
     /// This resets the cached configuration register value in this driver to
-    /// the power-up (reset) configuration of the device.
+    /// the power-up (reset) configuration of the device and forgets the
+    /// cached pointer register value.
     ///
     /// This needs to be called after performing a reset on the device, for
     /// example through an I2C general-call Reset command, which was not done
@@ -274,5 +325,297 @@ where
     /// and in the driver match.
     pub fn reset_internal_driver_state(&mut self) {
         self.config = Config::default();
+        self.config_is_known = true;
+        self.pointer = None;
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE> Tmp1x2<I2C, MODE, range::Dynamic>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Enable the extended measurement mode.
+    ///
+    /// This allows measurement of temperatures above 128°C.
+    pub async fn enable_extended_mode(&mut self) -> Result<(), Error<E>> {
+        self.write_extended_mode(true).await
+    }
+
+    /// Disable the extended measurement mode.
+    ///
+    /// This puts the device in normal measurement mode. It will not measure
+    /// temperatures above 128°C.
+    pub async fn disable_extended_mode(&mut self) -> Result<(), Error<E>> {
+        self.write_extended_mode(false).await
+    }
+
+    /// Set the high temperature threshold.
+    ///
+    /// The value provided will be capped to be in the interval
+    /// `[-128.0, 127.9375]` in normal mode and `[-256.0, 255.875]` in
+    /// extended mode.
+    pub async fn set_high_temperature_threshold(
+        &mut self,
+        temperature: f32,
+    ) -> Result<(), Error<E>> {
+        self.set_temperature_threshold(temperature, Register::THigh)
+            .await
+    }
+
+    /// Set the low temperature threshold.
+    ///
+    /// The value provided will be capped to be in the interval
+    /// `[-128.0, 127.9375]` in normal mode and `[-256.0, 255.875]` in
+    /// extended mode.
+    pub async fn set_low_temperature_threshold(
+        &mut self,
+        temperature: f32,
+    ) -> Result<(), Error<E>> {
+        self.set_temperature_threshold(temperature, Register::TLow)
+            .await
+    }
+
+    /// Compare the device configuration against a desired configuration
+    /// and write the registers that differ.
+    ///
+    /// Returns the differences found before writing.
+    /// See [`compare_configuration()`](#method.compare_configuration).
+    pub async fn reconcile_configuration(
+        &mut self,
+        desired: &Configuration,
+    ) -> Result<ConfigurationDiff, Error<E>> {
+        let actual = self.read_configuration_registers().await?;
+        let diff = self.configuration_diff(desired, &actual);
+        let expected = self.encode_configuration(desired);
+        if diff.config_register_differs() {
+            self.write_config(expected.config).await?;
+        } else {
+            self.config = expected.config;
+            self.config_is_known = true;
+        }
+        if diff.low_temperature_threshold.is_some() {
+            self.write_register(Register::TLow, expected.t_low).await?;
+        }
+        if diff.high_temperature_threshold.is_some() {
+            self.write_register(Register::THigh, expected.t_high)
+                .await?;
+        }
+        Ok(diff)
+    }
+
+    /// Apply a configuration transactionally.
+    ///
+    /// The current contents of the configuration and threshold registers are
+    /// read first. If writing the new configuration fails, the previous
+    /// contents are written back.
+    ///
+    /// See [`TransactionError`](enum.TransactionError.html) for the possible
+    /// outcomes in case of failure.
+    pub async fn apply_configuration(
+        &mut self,
+        configuration: &Configuration,
+    ) -> Result<(), TransactionError<E>> {
+        let snapshot = match self.read_configuration_registers().await {
+            Ok(snapshot) => snapshot,
+            Err(e) => return Err(TransactionError::Unchanged(bus_error(e))),
+        };
+        let registers = self.encode_configuration(configuration);
+        let error = match self.write_configuration_registers(registers).await {
+            Ok(()) => return Ok(()),
+            Err(e) => bus_error(e),
+        };
+        // The one-shot bit is not stored
+        let snapshot = ConfigurationRegisters {
+            config: snapshot.config.with_low_msb(BFH::ONE_SHOT),
+            ..snapshot
+        };
+        match self.write_configuration_registers(snapshot).await {
+            Ok(()) => Err(TransactionError::RolledBack(error)),
+            Err(rollback_error) => {
+                self.config_is_known = false;
+                Err(TransactionError::RollbackFailed {
+                    error,
+                    rollback_error: bus_error(rollback_error),
+                })
+            }
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Compare the device configuration against a desired configuration.
+    ///
+    /// This reads the configuration and threshold registers and returns
+    /// the settings that differ. The shutdown mode is expected to match the
+    /// conversion mode of the driver.
+    ///
+    /// Thresholds are compared as stored in the registers. If the extended
+    /// mode differs, thresholds may be reported as different even if they
+    /// represent the same temperature because their encoding differs.
+    pub async fn compare_configuration(
+        &mut self,
+        desired: &Configuration,
+    ) -> Result<ConfigurationDiff, Error<E>> {
+        let actual = self.read_configuration_registers().await?;
+        Ok(self.configuration_diff(desired, &actual))
+    }
+
+    async fn write_configuration_registers(
+        &mut self,
+        registers: ConfigurationRegisters,
+    ) -> Result<(), Error<E>> {
+        self.write_config(registers.config).await?;
+        self.write_register(Register::TLow, registers.t_low).await?;
+        self.write_register(Register::THigh, registers.t_high).await
+    }
+
+    pub(crate) async fn read_configuration_registers(
+        &mut self,
+    ) -> Result<ConfigurationRegisters, Error<E>> {
+        Ok(ConfigurationRegisters {
+            config: self.read_register_u16(Register::Config).await?,
+            t_low: self.read_register_u16(Register::TLow).await?,
+            t_high: self.read_register_u16(Register::THigh).await?,
+        })
+    }
+
+    /// Encode a configuration keeping the current conversion mode.
+    pub(crate) fn encode_configuration(&self, desired: &Configuration) -> ConfigurationRegisters {
+        let mut config = Config::default();
+        if (self.config.msb & BFH::SHUTDOWN) != 0 {
+            config = config.with_high_msb(BFH::SHUTDOWN);
+        }
+        if desired.extended_mode {
+            config = config.with_high_lsb(BFL::EXTENDED_MODE);
+        }
+        let conversion_rate = match desired.conversion_rate {
+            CR::_0_25Hz => 0,
+            CR::_1Hz => BFL::CONV_RATE0,
+            CR::_4Hz => BFL::CONV_RATE1,
+            CR::_8Hz => BFL::CONV_RATE1 | BFL::CONV_RATE0,
+        };
+        config.lsb = (config.lsb & !(BFL::CONV_RATE1 | BFL::CONV_RATE0)) | conversion_rate;
+        let fault_queue = match desired.fault_queue {
+            FaultQueue::_1 => 0,
+            FaultQueue::_2 => BFH::FAULT_QUEUE0,
+            FaultQueue::_4 => BFH::FAULT_QUEUE1,
+            FaultQueue::_6 => BFH::FAULT_QUEUE1 | BFH::FAULT_QUEUE0,
+        };
+        config.msb = (config.msb & !(BFH::FAULT_QUEUE1 | BFH::FAULT_QUEUE0)) | fault_queue;
+        if desired.alert_polarity == AlertPolarity::ActiveHigh {
+            config = config.with_high_msb(BFH::ALERT_POLARITY);
+        }
+        if desired.thermostat_mode == ThermostatMode::Interrupt {
+            config = config.with_high_msb(BFH::THERMOSTAT);
+        }
+        ConfigurationRegisters {
+            config,
+            t_low: encode_threshold(desired.low_temperature_threshold, desired.extended_mode),
+            t_high: encode_threshold(desired.high_temperature_threshold, desired.extended_mode),
+        }
+    }
+
+    fn configuration_diff(
+        &self,
+        desired: &Configuration,
+        actual: &ConfigurationRegisters,
+    ) -> ConfigurationDiff {
+        let expected = self.encode_configuration(desired);
+        let expected_config = ConfigRegister::from(expected.config);
+        let actual_config = ConfigRegister::from(actual.config.clone());
+        let threshold_mismatch = |expected: &RegisterU16, actual: &RegisterU16| {
+            // Only the bits used by the expected encoding are compared
+            let mask = if desired.extended_mode {
+                0b1111_1000
+            } else {
+                0b1111_0000
+            };
+            if expected.msb == actual.msb && expected.lsb == (actual.lsb & mask) {
+                return None;
+            }
+            Some(Mismatch {
+                expected: convert_temp_from_register_in_mode(
+                    expected.msb,
+                    expected.lsb,
+                    desired.extended_mode,
+                ),
+                actual: convert_temp_from_register_in_mode(
+                    actual.msb,
+                    actual.lsb,
+                    actual_config.is_extended_mode(),
+                ),
+            })
+        };
+        ConfigurationDiff {
+            is_shutdown: mismatch(expected_config.is_shutdown(), actual_config.is_shutdown()),
+            extended_mode: mismatch(
+                expected_config.is_extended_mode(),
+                actual_config.is_extended_mode(),
+            ),
+            conversion_rate: mismatch(
+                expected_config.conversion_rate(),
+                actual_config.conversion_rate(),
+            ),
+            fault_queue: mismatch(expected_config.fault_queue(), actual_config.fault_queue()),
+            alert_polarity: mismatch(
+                expected_config.alert_polarity(),
+                actual_config.alert_polarity(),
+            ),
+            thermostat_mode: mismatch(
+                expected_config.thermostat_mode(),
+                actual_config.thermostat_mode(),
+            ),
+            low_temperature_threshold: threshold_mismatch(&expected.t_low, &actual.t_low),
+            high_temperature_threshold: threshold_mismatch(&expected.t_high, &actual.t_high),
+        }
+    }
+}
+
+/// Contents of the configuration and threshold registers.
+pub(crate) struct ConfigurationRegisters {
+    pub(crate) config: Config,
+    pub(crate) t_low: RegisterU16,
+    pub(crate) t_high: RegisterU16,
+}
+
+fn encode_threshold(temperature: f32, extended_mode: bool) -> RegisterU16 {
+    let (msb, lsb) = if extended_mode {
+        convert_temp_to_register_extended(temperature)
+    } else {
+        convert_temp_to_register_normal(temperature)
+    };
+    RegisterU16 { lsb, msb }
+}
+
+fn bus_error<E>(error: Error<E>) -> E {
+    match error {
+        Error::I2C(e) => e,
+    }
+}
+
+fn mismatch<T: PartialEq>(expected: T, actual: T) -> Option<Mismatch<T>> {
+    if expected == actual {
+        None
+    } else {
+        Some(Mismatch { expected, actual })
     }
 }
@@ -1,8 +1,12 @@
 // functions to convert temperature to and from register values
 
 pub fn convert_temp_from_register(msb: u8, lsb: u8) -> f32 {
-    let mut sign = (u16::from(msb & 0b1000_0000)) << 8;
     let extended_mode = (lsb & 1) != 0;
+    convert_temp_from_register_in_mode(msb, lsb, extended_mode)
+}
+
+pub fn convert_temp_from_register_in_mode(msb: u8, lsb: u8, extended_mode: bool) -> f32 {
+    let mut sign = (u16::from(msb & 0b1000_0000)) << 8;
     if extended_mode {
         if sign != 0 {
             sign |= 0b1111_0000 << 8;
@@ -22,6 +26,17 @@ pub fn convert_temp_from_register(msb: u8, lsb: u8) -> f32 {
     }
 }
 
+pub fn convert_temp_from_register_msb(msb: u8, extended_mode: bool) -> i16 {
+    // the value is stored as two's complement
+    let value = i16::from(msb as i8);
+    if extended_mode {
+        value * 2
+    } else {
+        value
+    }
+}
+
+#[allow(clippy::manual_clamp)]
 pub fn convert_temp_to_register_normal(mut t: f32) -> (u8, u8) {
     if t > 127.9375 {
         t = 127.9375;
@@ -34,6 +49,7 @@ pub fn convert_temp_to_register_normal(mut t: f32) -> (u8, u8) {
     ((value >> 8) as u8, (value as u8 & 0b1111_0000))
 }
 
+#[allow(clippy::manual_clamp)]
 pub fn convert_temp_to_register_extended(mut t: f32) -> (u8, u8) {
     if t > 255.875 {
         t = 255.875;
@@ -47,9 +63,12 @@ pub fn convert_temp_to_register_extended(mut t: f32) -> (u8, u8) {
 }
 
 #[cfg(test)]
+#[allow(clippy::float_equality_without_abs, clippy::legacy_numeric_constants)]
 mod tests {
     use super::{
         convert_temp_from_register as convert_from_reg,
+        convert_temp_from_register_in_mode as convert_from_reg_in_mode,
+        convert_temp_from_register_msb as convert_from_msb,
         convert_temp_to_register_extended as convert_to_reg_ext,
         convert_temp_to_register_normal as convert_to_reg,
     };
@@ -107,6 +126,42 @@ mod tests {
         assert_near!(-256.0, convert_from_reg(0b1000_0000, 0b0000_0001));
     }
 
+    #[test]
+    fn can_convert_threshold_from_register() {
+        assert_near!(
+            80.0,
+            convert_from_reg_in_mode(0b0101_0000, 0b0000_0000, false)
+        );
+        assert_near!(
+            -0.25,
+            convert_from_reg_in_mode(0b1111_1111, 0b1100_0000, false)
+        );
+        assert_near!(
+            150.0,
+            convert_from_reg_in_mode(0b0100_1011, 0b0000_0000, true)
+        );
+        assert_near!(
+            -25.0,
+            convert_from_reg_in_mode(0b1111_0011, 0b1000_0000, true)
+        );
+    }
+
+    #[test]
+    fn can_convert_temperature_from_register_msb() {
+        assert_eq!(127, convert_from_msb(0b0111_1111, false));
+        assert_eq!(100, convert_from_msb(0b0110_0100, false));
+        assert_eq!(0, convert_from_msb(0b0000_0000, false));
+        assert_eq!(-1, convert_from_msb(0b1111_1111, false));
+        assert_eq!(-25, convert_from_msb(0b1110_0111, false));
+        assert_eq!(-128, convert_from_msb(0b1000_0000, false));
+        assert_eq!(254, convert_from_msb(0b0111_1111, true));
+        assert_eq!(150, convert_from_msb(0b0100_1011, true));
+        assert_eq!(0, convert_from_msb(0b0000_0000, true));
+        assert_eq!(-2, convert_from_msb(0b1111_1111, true));
+        assert_eq!(-56, convert_from_msb(0b1110_0100, true));
+        assert_eq!(-256, convert_from_msb(0b1000_0000, true));
+    }
+
     #[test]
     fn values_are_clamped() {
         assert_eq!((0b0111_1111, 0b1111_0000), convert_to_reg(129.0));
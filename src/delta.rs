@@ -0,0 +1,164 @@
+//! Change notification through a threshold window around the last reading.
+
+use crate::{
+    marker::{mode, range::MeasurementRange},
+    Error, Register, ThermostatMode, Tmp1x2,
+};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Reports temperature changes larger than a given delta through the
+/// alert pin.
+///
+/// The device is put in interrupt thermostat mode and the low and high
+/// temperature thresholds are set to a window of `±delta` around the current
+/// temperature. When the alert pin signals, call
+/// [`handle_alert()`](#method.handle_alert), which reads the new temperature,
+/// clears the alert and centers the window around it again.
+///
+/// In interrupt mode the device alternates between both thresholds: after
+/// the high threshold was exceeded, only falling below the low threshold
+/// would signal again, and vice versa. `handle_alert()` therefore re-arms
+/// the device by switching it through comparator mode, so that every change
+/// larger than the delta is reported, also several in the same direction.
+///
+/// The thresholds are limited to the measurement range of the driver.
+/// The device must be in continuous conversion mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaReporter {
+    delta: f32,
+    center: Option<f32>,
+}
+
+impl DeltaReporter {
+    /// Create a new reporter for changes larger than `delta` degrees Celsius.
+    ///
+    /// Returns `None` if the delta is not positive.
+    pub fn new(delta: f32) -> Option<Self> {
+        if delta.is_nan() || delta <= 0.0 {
+            return None;
+        }
+        Some(DeltaReporter {
+            delta,
+            center: None,
+        })
+    }
+
+    /// Temperature the window on the device is centered around, if known.
+    pub fn center(&self) -> Option<f32> {
+        self.center
+    }
+
+    /// Threshold of the window around `center` for the given register.
+    fn bound(&self, center: f32, register: Register) -> f32 {
+        match register {
+            Register::TLow => center - self.delta,
+            _ => center + self.delta,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "DeltaReporter",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl DeltaReporter {
+    /// Read the current temperature, set the threshold window around it and
+    /// set interrupt thermostat mode.
+    ///
+    /// Returns the temperature read.
+    pub async fn start<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<f32, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+        RANGE: MeasurementRange,
+    {
+        let temperature = self.recenter(dev).await?;
+        dev.set_thermostat_mode(ThermostatMode::Interrupt).await?;
+        Ok(temperature)
+    }
+
+    /// Read the current temperature, center the threshold window around it
+    /// and re-arm the alert for both directions.
+    ///
+    /// Reading the temperature also clears an active alert.
+    /// Returns the temperature read.
+    ///
+    /// If the window cannot be written completely, the previous window is
+    /// restored. If that fails as well, [`center()`](#method.center) returns
+    /// `None` until the next successful call.
+    ///
+    /// If switching back to interrupt mode fails, it is retried once. If the
+    /// retry fails as well, the error is returned and the device is left in
+    /// comparator mode, where the alert pin follows the window directly.
+    /// Calling [`start()`](#method.start) again sets interrupt mode.
+    pub async fn handle_alert<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<f32, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+        RANGE: MeasurementRange,
+    {
+        let temperature = self.recenter(dev).await?;
+        // After an alert in interrupt mode, the device only signals again
+        // once the opposite threshold is crossed. See the "Thermostat Mode
+        // (TM)" section and the "Output Transfer Function Diagrams" figure of
+        // the TMP102 datasheet (SBOS397). Comparator mode does not keep this
+        // state, so passing through it restarts the sequence.
+        dev.set_thermostat_mode(ThermostatMode::Comparator).await?;
+        if let Err(e) = dev.set_thermostat_mode(ThermostatMode::Interrupt).await {
+            dev.set_thermostat_mode(ThermostatMode::Interrupt)
+                .await
+                .map_err(|_| e)?;
+        }
+        Ok(temperature)
+    }
+
+    async fn recenter<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<f32, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+        RANGE: MeasurementRange,
+    {
+        let temperature = dev.read_temperature().await?;
+        // Move the bound in the direction of the change first so that the
+        // low threshold stays below the high threshold after every write.
+        // Without a previous window, the power-on thresholds are assumed,
+        // which are above the window.
+        let (first, second) = match self.center {
+            Some(center) if temperature > center => (Register::THigh, Register::TLow),
+            _ => (Register::TLow, Register::THigh),
+        };
+        dev.set_threshold_saturating(self.bound(temperature, first), first)
+            .await?;
+        if let Err(e) = dev
+            .set_threshold_saturating(self.bound(temperature, second), second)
+            .await
+        {
+            let restored = match self.center {
+                Some(center) => dev
+                    .set_threshold_saturating(self.bound(center, first), first)
+                    .await
+                    .is_ok(),
+                None => false,
+            };
+            if !restored {
+                self.center = None;
+            }
+            return Err(e);
+        }
+        self.center = Some(temperature);
+        Ok(temperature)
+    }
+}
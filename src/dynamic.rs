@@ -0,0 +1,167 @@
+//! Driver with a conversion mode selectable at runtime.
+
+use crate::{
+    marker::{mode, range},
+    BorrowingTmp1x2, BoundTmp1x2, ConversionMode, Error, SlaveAddr, Tmp1x2,
+};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Driver state in either conversion mode.
+#[derive(Debug)]
+enum State {
+    Continuous(BorrowingTmp1x2<mode::Continuous>),
+    OneShot(BorrowingTmp1x2<mode::OneShot>),
+}
+
+/// TMP1X2 device driver with a conversion mode selectable at runtime.
+///
+/// This owns the I²C bus and stores the [`Tmp1x2`](struct.Tmp1x2.html)
+/// driver state for either conversion mode, which allows changing the mode
+/// through a mutable reference.
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "DynTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+#[derive(Debug)]
+pub struct DynTmp1x2<I2C: AsyncI2c> {
+    i2c: I2C,
+    state: State,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "DynTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E> DynTmp1x2<I2C>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Create new instance of the TMP102 or TMP112x device.
+    ///
+    /// By default they are in continuous conversion mode.
+    pub fn new(i2c: I2C, address: SlaveAddr) -> Self {
+        Tmp1x2::new(i2c, address).into()
+    }
+
+    /// Destroy driver instance, return I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Get the current conversion mode.
+    pub fn mode(&self) -> ConversionMode {
+        match self.state {
+            State::Continuous(_) => ConversionMode::Continuous,
+            State::OneShot(_) => ConversionMode::OneShot,
+        }
+    }
+
+    /// Change the conversion mode.
+    ///
+    /// Nothing is done if the device is already in the requested mode.
+    /// If the mode change failed, the mode is unchanged.
+    pub async fn set_mode(&mut self, mode: ConversionMode) -> Result<(), Error<E>> {
+        let state = match (&mut self.state, mode) {
+            (State::Continuous(state), ConversionMode::OneShot) => {
+                state.bind(&mut self.i2c).config_one_shot().await?;
+                State::OneShot(state.with_mode())
+            }
+            (State::OneShot(state), ConversionMode::Continuous) => {
+                state.bind(&mut self.i2c).config_continuous().await?;
+                State::Continuous(state.with_mode())
+            }
+            _ => return Ok(()),
+        };
+        self.state = state;
+        Ok(())
+    }
+
+    /// Read the temperature from the sensor.
+    ///
+    /// In continuous conversion mode this never returns
+    /// `nb::Error::WouldBlock`. In one-shot conversion mode this behaves
+    /// like [`Tmp1x2::read_temperature()`](struct.Tmp1x2.html#method.read_temperature-1).
+    pub async fn read_temperature(&mut self) -> nb::Result<f32, Error<E>> {
+        match &mut self.state {
+            State::Continuous(state) => state
+                .bind(&mut self.i2c)
+                .read_temperature()
+                .await
+                .map_err(nb::Error::Other),
+            State::OneShot(state) => state.bind(&mut self.i2c).read_temperature().await,
+        }
+    }
+
+    /// Get the driver if in continuous conversion mode.
+    ///
+    /// The returned value dereferences to a [`Tmp1x2`](struct.Tmp1x2.html)
+    /// driver borrowing the I²C bus.
+    pub fn as_continuous_mut(
+        &mut self,
+    ) -> Option<BoundTmp1x2<'_, I2C, mode::Continuous, range::Dynamic>> {
+        match &mut self.state {
+            State::Continuous(state) => Some(state.bind(&mut self.i2c)),
+            State::OneShot(_) => None,
+        }
+    }
+
+    /// Get the driver if in one-shot conversion mode.
+    ///
+    /// The returned value dereferences to a [`Tmp1x2`](struct.Tmp1x2.html)
+    /// driver borrowing the I²C bus.
+    pub fn as_one_shot_mut(
+        &mut self,
+    ) -> Option<BoundTmp1x2<'_, I2C, mode::OneShot, range::Dynamic>> {
+        match &mut self.state {
+            State::OneShot(state) => Some(state.bind(&mut self.i2c)),
+            State::Continuous(_) => None,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "DynTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C: AsyncI2c> From<Tmp1x2<I2C, mode::Continuous>> for DynTmp1x2<I2C> {
+    fn from(dev: Tmp1x2<I2C, mode::Continuous>) -> Self {
+        let state = State::Continuous(BorrowingTmp1x2::from_driver(&dev));
+        DynTmp1x2 {
+            i2c: dev.destroy(),
+            state,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "DynTmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C: AsyncI2c> From<Tmp1x2<I2C, mode::OneShot>> for DynTmp1x2<I2C> {
+    fn from(dev: Tmp1x2<I2C, mode::OneShot>) -> Self {
+        let state = State::OneShot(BorrowingTmp1x2::from_driver(&dev));
+        DynTmp1x2 {
+            i2c: dev.destroy(),
+            state,
+        }
+    }
+}
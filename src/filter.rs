@@ -0,0 +1,382 @@
+//! Allocation-free filters for temperature readings.
+
+use crate::{marker::mode, Error, Tmp1x2};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Filter for a sequence of values.
+///
+/// Filters can be chained by putting them in a tuple: `(A, B)` feeds the
+/// output of `A` into `B`.
+///
+/// Values are `f32`. Integer values like raw register counts can be filtered
+/// as well, since they are converted to `f32` without loss.
+pub trait Filter {
+    /// Add a new value and return the filtered value.
+    fn update(&mut self, value: f32) -> f32;
+
+    /// Forget all previous values.
+    fn reset(&mut self);
+}
+
+impl<A: Filter, B: Filter> Filter for (A, B) {
+    fn update(&mut self, value: f32) -> f32 {
+        let value = self.0.update(value);
+        self.1.update(value)
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+/// Raw and filtered temperature
+///
+/// See [`read_temperature_filtered()`](struct.Tmp1x2.html#method.read_temperature_filtered).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FilteredReading {
+    /// Temperature read in degrees Celsius.
+    pub raw: f32,
+    /// Filtered temperature in degrees Celsius.
+    pub filtered: f32,
+}
+
+/// Fixed-capacity buffer of the last `N` values.
+#[derive(Debug, Clone, Copy)]
+struct Window<const N: usize> {
+    values: [f32; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> Window<N> {
+    const NOT_EMPTY: () = assert!(N > 0, "the window size must not be 0");
+
+    fn new() -> Self {
+        // Evaluated at compile time for each window size used
+        let () = Self::NOT_EMPTY;
+        Window {
+            values: [0.0; N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, value: f32) {
+        self.values[self.next] = value;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Values in the window, in no particular order.
+    fn values(&self) -> &[f32] {
+        &self.values[..self.len]
+    }
+
+    fn median(&self) -> f32 {
+        let mut sorted = self.values;
+        let sorted = &mut sorted[..self.len];
+        for i in 1..sorted.len() {
+            let mut j = i;
+            while j > 0 && sorted[j - 1] > sorted[j] {
+                sorted.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted[middle]
+        } else {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        }
+    }
+
+    fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
+
+/// Average of the last `N` values.
+///
+/// Until `N` values were added, the average of the values so far is
+/// returned. `N` must not be 0, which is checked at compile time:
+///
+/// ```compile_fail
+/// use tmp1x2::MovingAverage;
+///
+/// let filter = MovingAverage::<0>::new();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MovingAverage<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// Create a new filter.
+    pub fn new() -> Self {
+        MovingAverage {
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MovingAverage<N> {
+    fn update(&mut self, value: f32) -> f32 {
+        self.window.push(value);
+        let values = self.window.values();
+        values.iter().sum::<f32>() / values.len() as f32
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Exponential moving average.
+///
+/// Each new value is weighted with `alpha`, between 0 and 1, and the
+/// previous output with `1 - alpha`. The first value is returned unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialMovingAverage {
+    alpha: f32,
+    value: Option<f32>,
+}
+
+impl ExponentialMovingAverage {
+    /// Create a new filter.
+    ///
+    /// `alpha` is clamped to the range 0 to 1.
+    pub fn new(alpha: f32) -> Self {
+        ExponentialMovingAverage {
+            alpha: alpha.clamp(0.0, 1.0),
+            value: None,
+        }
+    }
+}
+
+impl Filter for ExponentialMovingAverage {
+    fn update(&mut self, value: f32) -> f32 {
+        let value = match self.value {
+            None => value,
+            Some(last) => last + self.alpha * (value - last),
+        };
+        self.value = Some(value);
+        value
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// Median of the last `N` values.
+///
+/// For an even number of values the mean of the two middle values is
+/// returned. `N` must not be 0, which is checked at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct MedianFilter<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> MedianFilter<N> {
+    /// Create a new filter.
+    pub fn new() -> Self {
+        MedianFilter {
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Default for MedianFilter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MedianFilter<N> {
+    fn update(&mut self, value: f32) -> f32 {
+        self.window.push(value);
+        self.window.median()
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Rejects values changing by more than a maximum step.
+///
+/// A rejected value is replaced by the last accepted value. After
+/// `max_rejections` consecutive rejections the next value is accepted, so
+/// that a real step change of the temperature passes after a delay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpikeRejection {
+    max_step: f32,
+    max_rejections: u8,
+    last: Option<f32>,
+    rejections: u8,
+}
+
+impl SpikeRejection {
+    /// Create a new filter.
+    pub fn new(max_step: f32, max_rejections: u8) -> Self {
+        SpikeRejection {
+            max_step,
+            max_rejections,
+            last: None,
+            rejections: 0,
+        }
+    }
+}
+
+impl Filter for SpikeRejection {
+    fn update(&mut self, value: f32) -> f32 {
+        if let Some(last) = self.last {
+            if libm::fabsf(value - last) > self.max_step && self.rejections < self.max_rejections {
+                self.rejections += 1;
+                return last;
+            }
+        }
+        self.last = Some(value);
+        self.rejections = 0;
+        value
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+        self.rejections = 0;
+    }
+}
+
+/// Rejects values deviating from the median of the last `N` values by more
+/// than a maximum deviation.
+///
+/// A rejected value is replaced by the median. All values, including
+/// rejected ones, are added to the window, so that the median follows a
+/// real step change of the temperature once it makes up half of the window.
+/// `N` must not be 0, which is checked at compile time.
+#[derive(Debug, Clone, Copy)]
+pub struct OutlierRejection<const N: usize> {
+    max_deviation: f32,
+    window: Window<N>,
+}
+
+impl<const N: usize> OutlierRejection<N> {
+    /// Create a new filter.
+    pub fn new(max_deviation: f32) -> Self {
+        OutlierRejection {
+            max_deviation,
+            window: Window::new(),
+        }
+    }
+}
+
+impl<const N: usize> Filter for OutlierRejection<N> {
+    fn update(&mut self, value: f32) -> f32 {
+        self.window.push(value);
+        let median = self.window.median();
+        if libm::fabsf(value - median) > self.max_deviation {
+            median
+        } else {
+            value
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::Continuous, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Read the temperature and pass it through a filter.
+    ///
+    /// The filter is not updated if the temperature cannot be read.
+    pub async fn read_temperature_filtered<F: Filter>(
+        &mut self,
+        filter: &mut F,
+    ) -> Result<FilteredReading, Error<E>> {
+        let raw = self.read_temperature().await?;
+        Ok(FilteredReading {
+            raw,
+            filtered: filter.update(raw),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run<F: Filter>(filter: &mut F, values: &[f32]) -> [f32; 5] {
+        let mut output = [0.0; 5];
+        for (output, value) in output.iter_mut().zip(values) {
+            *output = filter.update(*value);
+        }
+        output
+    }
+
+    #[test]
+    fn moving_average() {
+        let output = run(&mut MovingAverage::<3>::new(), &[1.0, 2.0, 3.0, 7.0, 8.0]);
+        assert_eq!([1.0, 1.5, 2.0, 4.0, 6.0], output);
+    }
+
+    #[test]
+    fn exponential_moving_average() {
+        let mut filter = ExponentialMovingAverage::new(0.5);
+        let output = run(&mut filter, &[4.0, 8.0, 8.0, 0.0, 2.0]);
+        assert_eq!([4.0, 6.0, 7.0, 3.5, 2.75], output);
+        filter.reset();
+        assert_eq!(10.0, filter.update(10.0));
+    }
+
+    #[test]
+    fn median() {
+        let output = run(&mut MedianFilter::<3>::new(), &[5.0, 1.0, 100.0, 3.0, 4.0]);
+        assert_eq!([5.0, 3.0, 5.0, 3.0, 4.0], output);
+    }
+
+    #[test]
+    fn spike_rejection() {
+        let mut filter = SpikeRejection::new(1.0, 2);
+        let output = run(&mut filter, &[20.0, 30.0, 20.5, 25.0, 25.0]);
+        assert_eq!([20.0, 20.0, 20.5, 20.5, 20.5], output);
+        assert_eq!(25.0, filter.update(25.0));
+    }
+
+    #[test]
+    fn outlier_rejection() {
+        let mut filter = OutlierRejection::<3>::new(1.0);
+        let output = run(&mut filter, &[20.0, 20.5, 30.0, 21.0, 30.0]);
+        assert_eq!([20.0, 20.5, 20.5, 21.0, 30.0], output);
+    }
+
+    #[test]
+    fn chained_filters() {
+        let mut filter = (MedianFilter::<3>::new(), MovingAverage::<2>::new());
+        let output = run(&mut filter, &[2.0, 2.0, 100.0, 4.0, 4.0]);
+        assert_eq!([2.0, 2.0, 2.0, 3.0, 4.0], output);
+    }
+}
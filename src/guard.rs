@@ -0,0 +1,213 @@
+//! Over-temperature shutdown combining hardware alert and software readings.
+
+use crate::{
+    marker::mode, AlertPolarity, ConfigRegister, Error, FaultQueue, ThermostatError,
+    ThermostatOutput, Tmp1x2,
+};
+use embedded_hal::digital::InputPin;
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Settings for [`OverTemperatureGuard`](struct.OverTemperatureGuard.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuardSettings {
+    /// Shut down when the temperature reaches this value in degrees Celsius.
+    pub critical_limit: f32,
+    /// The hardware alert is released when the temperature falls below
+    /// `critical_limit - hysteresis`.
+    pub hysteresis: f32,
+    /// Level of the alert pin while the critical limit is exceeded.
+    pub alert_polarity: AlertPolarity,
+    /// Consecutive measurements above the limit needed to set the hardware
+    /// alert.
+    pub fault_queue: FaultQueue,
+    /// Shut down when this many consecutive reads fail. 0 is treated as 1.
+    pub max_failed_reads: u8,
+}
+
+/// Reason for an over-temperature shutdown
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ShutdownReason {
+    /// A reading reached the critical limit.
+    OverTemperature {
+        /// Temperature read in degrees Celsius
+        temperature: f32,
+    },
+    /// The hardware alert is active.
+    HardwareAlert,
+    /// The sensor did not respond for the configured number of reads.
+    SensorFailure,
+}
+
+/// Guard calling a shutdown function when the temperature is too high.
+///
+/// [`arm()`](#method.arm) programs the device as a comparator-mode
+/// thermostat with the critical limit as high threshold, so that the alert
+/// pin can cut the power in hardware even if the firmware hangs.
+///
+/// [`check()`](#method.check) reads the temperature from the device, and
+/// [`check_alert_pin()`](#method.check_alert_pin) reads the alert pin
+/// directly. The shutdown function is called with the reason when a reading
+/// reaches the critical limit, when the hardware alert is active or when the
+/// configured number of consecutive reads failed.
+///
+/// The shutdown function is called only once. Afterwards the guard stays
+/// tripped until [`reset()`](#method.reset) is called.
+#[derive(Debug)]
+pub struct OverTemperatureGuard<F> {
+    settings: GuardSettings,
+    on_shutdown: F,
+    failed_reads: u8,
+    tripped: Option<ShutdownReason>,
+}
+
+impl<F> OverTemperatureGuard<F>
+where
+    F: FnMut(ShutdownReason),
+{
+    /// Create a new guard calling `on_shutdown` when tripped.
+    pub fn new(settings: GuardSettings, on_shutdown: F) -> Self {
+        OverTemperatureGuard {
+            settings,
+            on_shutdown,
+            failed_reads: 0,
+            tripped: None,
+        }
+    }
+
+    /// Destroy the guard, return the shutdown function.
+    pub fn destroy(self) -> F {
+        self.on_shutdown
+    }
+
+    /// Current settings.
+    pub fn settings(&self) -> &GuardSettings {
+        &self.settings
+    }
+
+    /// Reason of the shutdown, if the guard was tripped.
+    pub fn tripped(&self) -> Option<ShutdownReason> {
+        self.tripped
+    }
+
+    /// Rearm the guard after a shutdown.
+    ///
+    /// This also clears the failed read count.
+    pub fn reset(&mut self) {
+        self.tripped = None;
+        self.failed_reads = 0;
+    }
+
+    /// Read the state of the alert pin and trip the guard if it is active.
+    ///
+    /// The pin level is interpreted according to the configured alert
+    /// polarity. Returns whether the alert is active.
+    pub fn check_alert_pin<P: InputPin>(&mut self, pin: &mut P) -> Result<bool, P::Error> {
+        let active = match self.settings.alert_polarity {
+            AlertPolarity::ActiveHigh => pin.is_high()?,
+            AlertPolarity::ActiveLow => pin.is_low()?,
+        };
+        if active {
+            self.trip(ShutdownReason::HardwareAlert);
+        }
+        Ok(active)
+    }
+
+    fn trip(&mut self, reason: ShutdownReason) {
+        if self.tripped.is_none() {
+            self.tripped = Some(reason);
+            (self.on_shutdown)(reason);
+        }
+    }
+
+    fn record_failure<E>(&mut self, error: Error<E>) -> Error<E> {
+        self.failed_reads = self.failed_reads.saturating_add(1);
+        if self.failed_reads >= self.settings.max_failed_reads.max(1) {
+            self.trip(ShutdownReason::SensorFailure);
+        }
+        error
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "OverTemperatureGuard",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<F> OverTemperatureGuard<F>
+where
+    F: FnMut(ShutdownReason),
+{
+    /// Program the device thresholds so that the alert pin is active while
+    /// the critical limit is exceeded.
+    ///
+    /// See [`configure_as_thermostat()`](struct.Tmp1x2.html#method.configure_as_thermostat).
+    pub async fn arm<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<(), ThermostatError<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        let s = self.settings;
+        dev.configure_as_thermostat(
+            s.critical_limit,
+            s.hysteresis,
+            ThermostatOutput::Cooler,
+            s.alert_polarity,
+            s.fault_queue,
+        )
+        .await
+    }
+
+    /// Read the temperature and trip the guard if necessary.
+    ///
+    /// The comparator-mode alert status is only read from the device if the
+    /// configuration cached in the driver is unknown, for example after a
+    /// failed rollback in
+    /// [`apply_configuration()`](struct.Tmp1x2.html#method.apply_configuration).
+    /// Otherwise the critical limit set in [`arm()`](#method.arm) is checked
+    /// against the temperature read. Use
+    /// [`check_alert_pin()`](#method.check_alert_pin) to follow the hardware
+    /// alert on every call.
+    ///
+    /// Read errors are returned and counted. The count is cleared after a
+    /// successful check. Returns the temperature read.
+    pub async fn check<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<f32, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        let temperature = dev
+            .read_temperature()
+            .await
+            .map_err(|e| self.record_failure(e))?;
+        if temperature >= self.settings.critical_limit {
+            self.trip(ShutdownReason::OverTemperature { temperature });
+        }
+        // While the configuration cached in the driver is known, the alert
+        // status follows the critical limit programmed by `arm()`, which is
+        // already compared against above.
+        if !dev.config_is_known {
+            let config = dev
+                .current_config()
+                .await
+                .map_err(|e| self.record_failure(e))?;
+            let config = ConfigRegister::from(config);
+            let active_high = config.alert_polarity() == AlertPolarity::ActiveHigh;
+            if active_high == config.alert_bit() {
+                self.trip(ShutdownReason::HardwareAlert);
+            }
+        }
+        self.failed_reads = 0;
+        Ok(temperature)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::RegisterU16;
-use crate::{Config, Error, Register, Tmp1x2};
+use crate::{BitFlagsHigh as BFH, Config, Error, Register, Tmp1x2};
 #[cfg(not(feature = "async"))]
 use embedded_hal::i2c::I2c;
 #[cfg(feature = "async")]
@@ -13,39 +13,95 @@ use embedded_hal_async::i2c::I2c as AsyncI2c;
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E, MODE> Tmp1x2<I2C, MODE>
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
     pub(crate) async fn write_config(&mut self, data: Config) -> Result<(), Error<E>> {
-        self.write_register(Register::CONFIG, data.clone()).await?;
+        self.write_register(Register::Config, data.clone()).await?;
         self.config = data;
+        self.config_is_known = true;
         Ok(())
     }
 
+    /// Get the cached configuration.
+    ///
+    /// If the cached configuration is unknown, it is read back from the
+    /// device first. The shutdown bit is kept since the conversion mode is
+    /// determined by the driver type.
+    pub(crate) async fn current_config(&mut self) -> Result<Config, Error<E>> {
+        if !self.config_is_known {
+            let data = self.read_register_u16(Register::Config).await?;
+            let shutdown = self.config.msb & BFH::SHUTDOWN;
+            self.config = Config {
+                // The one-shot bit is not stored
+                msb: (data.msb & !BFH::SHUTDOWN & !BFH::ONE_SHOT) | shutdown,
+                lsb: data.lsb,
+            };
+            self.config_is_known = true;
+        }
+        Ok(self.config.clone())
+    }
+
     pub(crate) async fn write_register(
         &mut self,
-        register: u8,
+        register: Register,
         data: RegisterU16,
     ) -> Result<(), Error<E>> {
-        self.i2c
-            .write(self.address, &[register, data.msb, data.lsb])
+        let result = self
+            .i2c
+            .write(self.address, &[register.addr(), data.msb, data.lsb])
             .await
-            .map_err(Error::I2C)
+            .map_err(Error::I2C);
+        self.update_pointer(register, result.is_ok());
+        result
     }
 
     pub(crate) async fn read_register_u16(
         &mut self,
-        register: u8,
+        register: Register,
     ) -> Result<RegisterU16, Error<E>> {
         let mut data = [0; 2];
-        self.i2c
-            .write_read(self.address, &[register], &mut data)
-            .await
-            .map_err(Error::I2C)?;
+        self.read_register_data(register, &mut data).await?;
         Ok(RegisterU16 {
             msb: data[0],
             lsb: data[1],
         })
     }
+
+    pub(crate) async fn read_register_msb(&mut self, register: Register) -> Result<u8, Error<E>> {
+        let mut data = [0];
+        self.read_register_data(register, &mut data).await?;
+        Ok(data[0])
+    }
+
+    /// Read the first bytes of a register.
+    ///
+    /// If the device pointer register already points to the register,
+    /// the pointer byte is not sent again.
+    async fn read_register_data(
+        &mut self,
+        register: Register,
+        data: &mut [u8],
+    ) -> Result<(), Error<E>> {
+        let result = if self.pointer == Some(register) {
+            self.i2c.read(self.address, data).await
+        } else {
+            self.i2c
+                .write_read(self.address, &[register.addr()], data)
+                .await
+        };
+        self.update_pointer(register, result.is_ok());
+        result.map_err(Error::I2C)
+    }
+
+    fn update_pointer(&mut self, register: Register, transfer_succeeded: bool) {
+        // After a failed transfer it is unknown whether the device received
+        // the pointer byte so it must be sent again next time.
+        self.pointer = if transfer_succeeded {
+            Some(register)
+        } else {
+            None
+        };
+    }
 }
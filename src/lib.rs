@@ -6,16 +6,54 @@
 //!
 //! This driver allows you to:
 //! - Change into one-shot or continuous conversion mode.
+//! - Select the conversion mode at runtime with `DynTmp1x2`.
+//! - Share the I²C bus without giving it to the driver with `BorrowingTmp1x2`.
 //! - Read the temperature.
+//! - Read the temperature in whole degrees with a single-byte transfer.
+//! - Read only new conversion results in continuous mode using a clock.
+//! - Sample adaptively, changing between one-shot and continuous mode
+//!   depending on the rate of change of the temperature.
 //! - Enable/disable the extended measurement mode.
+//! - Track the measurement range in the driver type and check thresholds
+//!   against it.
 //! - Trigger a one-shot measurement.
 //! - Read whether the one-shot measurement result is ready.
+//! - Average a burst of one-shot measurements.
+//! - Filter readings with moving average, exponential moving average and
+//!   median filters and reject spikes and outliers, without allocation.
+//! - Estimate the rate of change of the temperature, detect fast rises and
+//!   falls and predict the time until a threshold is reached.
+//! - Fit a first-order thermal model online and predict the steady-state
+//!   temperature.
+//! - Track the minimum, maximum, mean and variance of the readings and the time
+//!   spent above and below thresholds for periodic telemetry reports.
+//! - Accumulate Arrhenius thermal aging as equivalent hours at a reference
+//!   temperature and remaining life, and store it across reboots.
 //! - Set the conversion rate.
+//! - Calculate sample periods, conversion times and worst-case alert latency,
+//!   and select the slowest conversion rate meeting a required latency.
+//! - Estimate the average supply current and energy per reading of a
+//!   configuration.
 //! - Set the high/low temperature threshold.
 //! - Set the fault queue.
 //! - Set the alert polarity.
 //! - Set the thermostat mode.
+//! - Configure the device as a standalone heater or cooler thermostat.
 //! - Read whether a comparator mode alert is active.
+//! - Get notified through the alert pin when the temperature changes by more
+//!   than a given delta.
+//! - Switch a heater or cooler on and off through a digital output pin with
+//!   hysteresis and minimum on/off times.
+//! - Regulate the temperature with a PID controller driving a PWM output.
+//! - Raise latching warning and critical alarms with hysteresis and debouncing,
+//!   and record their transitions in an event queue.
+//! - Call a shutdown function when the temperature exceeds a critical limit,
+//!   the hardware alert is active or the sensor stops responding.
+//! - Read and write raw register values for diagnostics.
+//! - Read and decode all registers at once.
+//! - Compare the device configuration against a desired configuration and
+//!   write only the registers that differ.
+//! - Apply a configuration transactionally with rollback on failure.
 //!
 //! ## The devices
 //!
@@ -70,6 +108,188 @@
 //! # }
 //! ```
 //!
+//! ### Read only new conversion results in continuous mode
+//!
+//! Any monotonic clock returning a `core::time::Duration` can be used.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use std::time::Instant;
+//! use tmp1x2::{FreshSampleTracker, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let start = Instant::now();
+//! let mut tracker = FreshSampleTracker::new(|| start.elapsed());
+//! loop {
+//!     let temperature = nb::block!(tracker.read_fresh_temperature(&mut sensor)).unwrap();
+//!     println!("Temperature: {}", temperature);
+//! }
+//! # }
+//! ```
+//!
+//! ### Sample adaptively depending on the rate of change
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use core::time::Duration;
+//! use linux_embedded_hal::I2cdev;
+//! use std::time::Instant;
+//! use tmp1x2::{
+//!     AdaptiveSampler, AdaptiveSamplingSettings, ConversionRate, DynTmp1x2, SlaveAddr,
+//! };
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let sensor = DynTmp1x2::new(dev, SlaveAddr::default());
+//! let settings = AdaptiveSamplingSettings {
+//!     slow_interval: Duration::from_secs(60),
+//!     fast_rate: ConversionRate::_4Hz,
+//!     fast_threshold: 0.05,
+//!     settle_threshold: 0.02,
+//!     settle_time: Duration::from_secs(30),
+//! };
+//! let start = Instant::now();
+//! let mut sampler = AdaptiveSampler::new(sensor, || start.elapsed(), settings);
+//! loop {
+//!     let temperature = nb::block!(sampler.read_temperature()).unwrap();
+//!     println!("Temperature: {}", temperature);
+//! }
+//! # }
+//! ```
+//!
+//! ### Filter the temperature readings
+//!
+//! Outliers are removed with a median filter and the result is smoothed
+//! with an exponential moving average.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{ExponentialMovingAverage, MedianFilter, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let mut filter = (MedianFilter::<5>::new(), ExponentialMovingAverage::new(0.2));
+//! let reading = sensor.read_temperature_filtered(&mut filter).unwrap();
+//! println!("Raw: {}, filtered: {}", reading.raw, reading.filtered);
+//! # }
+//! ```
+//!
+//! ### Detect a fast temperature rise
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use std::time::Instant;
+//! use tmp1x2::{Trend, TrendEstimator, TrendSettings, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let settings = TrendSettings {
+//!     rising_rate: 0.1,
+//!     falling_rate: 0.1,
+//! };
+//! let start = Instant::now();
+//! let mut estimator = TrendEstimator::<_, 8>::new(|| start.elapsed(), settings);
+//! loop {
+//!     sensor.read_temperature_into(&mut estimator).unwrap();
+//!     if estimator.trend() == Some(Trend::RisingFast) {
+//!         println!("Rising fast. 60°C in {:?}", estimator.time_to_threshold(60.0));
+//!     }
+//!     std::thread::sleep(std::time::Duration::from_secs(1));
+//! }
+//! # }
+//! ```
+//!
+//! ### Predict the steady-state temperature
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use std::time::Instant;
+//! use tmp1x2::{ThermalModel, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let start = Instant::now();
+//! let mut model = ThermalModel::new(|| start.elapsed(), 0.98);
+//! loop {
+//!     sensor.read_temperature_into(&mut model).unwrap();
+//!     if let Some(prediction) = model.prediction() {
+//!         println!(
+//!             "Final temperature: {} (confidence {})",
+//!             prediction.temperature, prediction.confidence
+//!         );
+//!     }
+//!     std::thread::sleep(std::time::Duration::from_secs(10));
+//! }
+//! # }
+//! ```
+//!
+//! ### Report statistics of the readings every hour
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use std::time::{Duration, Instant};
+//! use tmp1x2::{StatisticsTracker, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let start = Instant::now();
+//! let mut tracker = StatisticsTracker::new(|| start.elapsed(), 5.0, 40.0);
+//! let mut next_report = Duration::from_secs(3600);
+//! loop {
+//!     sensor.read_temperature_into(&mut tracker).unwrap();
+//!     if start.elapsed() >= next_report {
+//!         println!("{:?}", tracker.take_snapshot());
+//!         next_report += Duration::from_secs(3600);
+//!     }
+//!     std::thread::sleep(Duration::from_secs(1));
+//! }
+//! # }
+//! ```
+//!
+//! ### Estimate the consumed component life
+//!
+//! The state is restored from and regularly saved to persistent storage.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use std::time::{Duration, Instant};
+//! use tmp1x2::{AgingAccumulator, AgingSettings, AgingState, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let settings = AgingSettings {
+//!     activation_energy: 0.7,
+//!     reference_temperature: 55.0,
+//!     rated_life: Duration::from_secs(50_000 * 3600),
+//! };
+//! let state = std::fs::read("aging.bin")
+//!     .ok()
+//!     .and_then(|bytes| AgingState::from_bytes(&bytes.try_into().ok()?))
+//!     .unwrap_or_default();
+//! let start = Instant::now();
+//! let mut aging = AgingAccumulator::with_state(|| start.elapsed(), settings, state);
+//! loop {
+//!     sensor.read_temperature_into(&mut aging).unwrap();
+//!     println!("Remaining life: {}%", aging.remaining_life_percent());
+//!     std::fs::write("aging.bin", aging.state().to_bytes()).unwrap();
+//!     std::thread::sleep(Duration::from_secs(60));
+//! }
+//! # }
+//! ```
+//!
 //! ### Provide an alternative address
 //!
 //! ```no_run
@@ -101,6 +321,24 @@
 //! # }
 //! ```
 //!
+//! ### Average a burst of one-shot measurements
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::{Delay, I2cdev};
+//! use core::num::NonZeroU8;
+//! use tmp1x2::{Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let mut sensor = sensor.into_one_shot().ok().unwrap();
+//! let count = NonZeroU8::new(8).unwrap();
+//! let stats = sensor.read_temperature_burst(&mut Delay, count).unwrap();
+//! println!("Temperature: {} +/- {}", stats.mean, stats.standard_deviation);
+//! # }
+//! ```
+//!
 //! ### Get the device back if there was an error during a mode change
 //!
 //! ```no_run
@@ -122,6 +360,36 @@
 //! # }
 //! ```
 //!
+//! ### Select the conversion mode at runtime
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{ConversionMode, DynTmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = DynTmp1x2::new(dev, SlaveAddr::default());
+//! sensor.set_mode(ConversionMode::OneShot).unwrap();
+//! let temperature = nb::block!(sensor.read_temperature()).unwrap();
+//! # }
+//! ```
+//!
+//! ### Share the I²C bus with other drivers
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{BorrowingTmp1x2, SlaveAddr};
+//!
+//! let mut dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = BorrowingTmp1x2::new(SlaveAddr::default());
+//! let temperature = sensor.bind(&mut dev).read_temperature().unwrap();
+//! // `dev` can be used for other devices here.
+//! # }
+//! ```
+//!
 //! ### Enable the extended measurement mode
 //!
 //! ```no_run
@@ -136,6 +404,26 @@
 //! # }
 //! ```
 //!
+//! ### Track the measurement range in the driver type
+//!
+//! Threshold values are checked against the range at compile time when
+//! they are constants.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{ExtendedThreshold, Tmp1x2, SlaveAddr};
+//!
+//! const HIGH: ExtendedThreshold = ExtendedThreshold::from_celsius(200);
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let mut sensor = sensor.into_extended().ok().unwrap();
+//! sensor.set_high_temperature_threshold(HIGH).unwrap();
+//! # }
+//! ```
+//!
 //! ### Set the conversion rate to 1Hz
 //!
 //! ```no_run
@@ -150,6 +438,41 @@
 //! # }
 //! ```
 //!
+//! ### Select the slowest conversion rate meeting a required alert latency
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use core::time::Duration;
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{ConversionRate, FaultQueue, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let fault_queue = FaultQueue::_2;
+//! let rate = ConversionRate::slowest_meeting_latency(fault_queue, Duration::from_secs(1))
+//!     .unwrap();
+//! sensor.set_fault_queue(fault_queue).unwrap();
+//! sensor.set_conversion_rate(rate).unwrap();
+//! # }
+//! ```
+//!
+//! ### Estimate the supply current of a configuration
+//!
+//! ```
+//! use core::time::Duration;
+//! use tmp1x2::{ConversionMode, ConversionRate, PowerProfile};
+//!
+//! let estimate = PowerProfile::TMP102.estimate(
+//!     ConversionMode::OneShot,
+//!     ConversionRate::default(),
+//!     Duration::from_secs(10),
+//!     400_000,
+//! );
+//! println!("Average current: {} µA", estimate.average_current);
+//! println!("Energy per reading: {} µJ", estimate.energy_per_reading);
+//! ```
+//!
 //! ### Set the high and low temperature thresholds
 //!
 //! ```no_run
@@ -209,6 +532,32 @@
 //! # }
 //! ```
 //!
+//! ### Drive a fan directly from the alert pin
+//!
+//! The fan is turned on at 40°C and turned off below 37°C. The alert pin
+//! is high while the fan should be on. Four consecutive measurements are
+//! needed to switch.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{AlertPolarity, FaultQueue, ThermostatOutput, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! sensor
+//!     .configure_as_thermostat(
+//!         40.0,
+//!         3.0,
+//!         ThermostatOutput::Cooler,
+//!         AlertPolarity::ActiveHigh,
+//!         FaultQueue::_4,
+//!     )
+//!     .unwrap();
+//! # }
+//! ```
+//!
 //! ### Check whether an alert is active as defined by the comparator mode
 //!
 //! Note that this ignores the thermostat mode setting and always refers to
@@ -225,6 +574,218 @@
 //! let alert = sensor.is_comparator_mode_alert_active().unwrap();
 //! # }
 //! ```
+//!
+//! ### Get notified when the temperature changes by more than 0.5°C
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{DeltaReporter, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let mut reporter = DeltaReporter::new(0.5).unwrap();
+//! let temperature = reporter.start(&mut sensor).unwrap();
+//! // ...after the alert pin signals:
+//! let temperature = reporter.handle_alert(&mut sensor).unwrap();
+//! # }
+//! ```
+//!
+//! ### Switch a heater on and off through an output pin
+//!
+//! The heater is switched on below 21°C and off at 22°C. It stays on and
+//! off for at least a minute.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use core::time::Duration;
+//! use linux_embedded_hal::{CdevPin, I2cdev};
+//! # use linux_embedded_hal::gpio_cdev::{Chip, LineRequestFlags};
+//! use std::time::Instant;
+//! use tmp1x2::{OnOffController, OnOffSettings, ThermostatOutput, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! # let mut chip = Chip::new("/dev/gpiochip0").unwrap();
+//! # let handle = chip.get_line(17).unwrap().request(LineRequestFlags::OUTPUT, 0, "heater").unwrap();
+//! let heater_pin = CdevPin::new(handle).unwrap();
+//! let settings = OnOffSettings {
+//!     setpoint: 21.0,
+//!     hysteresis: 1.0,
+//!     output: ThermostatOutput::Heater,
+//!     min_on_time: Duration::from_secs(60),
+//!     min_off_time: Duration::from_secs(60),
+//!     failsafe_on: false,
+//! };
+//! let start = Instant::now();
+//! let mut controller = OnOffController::new(heater_pin, || start.elapsed(), settings);
+//! loop {
+//!     let heating = controller.update(&mut sensor).unwrap();
+//!     std::thread::sleep(Duration::from_millis(250));
+//! }
+//! # }
+//! ```
+//!
+//! ### Regulate the temperature with a PID controller driving a fan
+//!
+//! The controller is updated once per conversion. If the temperature cannot
+//! be read, the fan runs at full speed.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! # use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+//! # struct Pwm;
+//! # impl ErrorType for Pwm { type Error = core::convert::Infallible; }
+//! # impl SetDutyCycle for Pwm {
+//! #     fn max_duty_cycle(&self) -> u16 { 1000 }
+//! #     fn set_duty_cycle(&mut self, _: u16) -> Result<(), Self::Error> { Ok(()) }
+//! # }
+//! # let fan_pwm = Pwm;
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{ConversionRate, PidController, PidSettings, ThermostatOutput, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let settings = PidSettings {
+//!     setpoint: 35.0,
+//!     kp: 0.2,
+//!     ki: 0.01,
+//!     kd: 0.5,
+//!     output: ThermostatOutput::Cooler,
+//!     output_min: 0.2,
+//!     output_max: 1.0,
+//!     failsafe_output: 1.0,
+//! };
+//! let mut controller = PidController::new(fan_pwm, settings);
+//! sensor.set_conversion_rate(ConversionRate::_1Hz).unwrap();
+//! loop {
+//!     let duty = controller.update(&mut sensor).unwrap();
+//!     std::thread::sleep(ConversionRate::_1Hz.sample_period());
+//! }
+//! # }
+//! ```
+//!
+//! ### Raise latching alarms and acknowledge them
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{AlarmEvent, AlarmLevel, AlarmManager, AlarmSettings, FaultQueue, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let warning = AlarmSettings {
+//!     threshold: 50.0,
+//!     hysteresis: 2.0,
+//!     debounce: FaultQueue::_4,
+//! };
+//! let critical = AlarmSettings {
+//!     threshold: 70.0,
+//!     hysteresis: 2.0,
+//!     debounce: FaultQueue::_2,
+//! };
+//! let mut alarms = AlarmManager::<8>::new(warning, critical);
+//! loop {
+//!     alarms.update(&mut sensor).unwrap();
+//!     while let Some(event) = alarms.pop_event() {
+//!         println!("{:?}", event);
+//!         if let AlarmEvent::Raised { level: AlarmLevel::Warning, .. } = event {
+//!             alarms.acknowledge(AlarmLevel::Warning);
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! ### Cut the power when the temperature is too high
+//!
+//! The alert pin is also programmed to go low at 85°C so that it can cut
+//! the power in hardware as a backup.
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{AlertPolarity, FaultQueue, GuardSettings, OverTemperatureGuard, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let settings = GuardSettings {
+//!     critical_limit: 85.0,
+//!     hysteresis: 5.0,
+//!     alert_polarity: AlertPolarity::ActiveLow,
+//!     fault_queue: FaultQueue::_2,
+//!     max_failed_reads: 3,
+//! };
+//! let mut guard = OverTemperatureGuard::new(settings, |reason| {
+//!     println!("Shutting down: {:?}", reason);
+//!     // Cut the power here.
+//! });
+//! guard.arm(&mut sensor).unwrap();
+//! loop {
+//!     let _ = guard.check(&mut sensor);
+//!     std::thread::sleep(std::time::Duration::from_millis(250));
+//! }
+//! # }
+//! ```
+//!
+//! ### Read a raw register value for diagnostics
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{ConfigRegister, Register, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let config = sensor.read_register_raw(Register::Config).unwrap();
+//! let config = ConfigRegister::from_bits(config);
+//! println!("Conversion rate: {:?}", config.conversion_rate());
+//! # }
+//! ```
+//!
+//! ### Print a report of all register contents
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let dump = sensor.dump().unwrap();
+//! println!("{}", dump);
+//! # }
+//! ```
+//!
+//! ### Check the device configuration and restore it if it differs
+//!
+//! ```no_run
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use linux_embedded_hal::I2cdev;
+//! use tmp1x2::{Configuration, ConversionRate, Tmp1x2, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp1x2::new(dev, SlaveAddr::default());
+//! let desired = Configuration {
+//!     conversion_rate: ConversionRate::_1Hz,
+//!     high_temperature_threshold: 60.0,
+//!     ..Default::default()
+//! };
+//! let diff = sensor.compare_configuration(&desired).unwrap();
+//! if !diff.is_empty() {
+//!     println!("Configuration differs: {:?}", diff);
+//!     sensor.reconcile_configuration(&desired).unwrap();
+//! }
+//! # }
+//! ```
 
 #![deny(unsafe_code)]
 #![deny(missing_docs)]
@@ -256,8 +817,102 @@ pub enum ModeChangeError<E, DEV> {
     I2C(E, DEV),
 }
 
+/// Error type for transactional configuration changes.
+///
+/// See [`apply_configuration()`](struct.Tmp1x2.html#method.apply_configuration).
+#[derive(Debug)]
+pub enum TransactionError<E> {
+    /// I²C bus error before any change was applied.
+    ///
+    /// The device configuration is unchanged.
+    Unchanged(E),
+    /// I²C bus error while applying the changes.
+    ///
+    /// The previous device configuration was restored.
+    RolledBack(E),
+    /// I²C bus error while applying the changes and while restoring the
+    /// previous device configuration.
+    ///
+    /// The device configuration is unknown. The configuration cached in the
+    /// driver will be read back from the device before it is modified again.
+    RollbackFailed {
+        /// Error that happened while applying the changes.
+        error: E,
+        /// Error that happened while restoring the previous configuration.
+        rollback_error: E,
+    },
+}
+
+/// Error type for bursts of one-shot measurements.
+///
+/// See [`read_temperature_burst()`](struct.Tmp1x2.html#method.read_temperature_burst).
+#[derive(Debug)]
+pub enum BurstError<E> {
+    /// I²C bus error
+    I2C(E),
+    /// A measurement was not finished within three times the maximum
+    /// conversion time.
+    ///
+    /// The measurement stays pending, so the next read continues waiting for
+    /// it.
+    Timeout,
+}
+
+impl<E> From<Error<E>> for BurstError<E> {
+    fn from(error: Error<E>) -> Self {
+        match error {
+            Error::I2C(e) => BurstError::I2C(e),
+        }
+    }
+}
+
+/// Error type for standalone thermostat setup.
+///
+/// See [`configure_as_thermostat()`](struct.Tmp1x2.html#method.configure_as_thermostat).
+#[derive(Debug)]
+pub enum ThermostatError<E> {
+    /// I²C bus error
+    I2C(E),
+    /// The hysteresis is not positive or the thresholds are not
+    /// representable as distinct values in the current measurement range.
+    ///
+    /// Nothing was written to the device.
+    InvalidThresholds,
+}
+
+impl<E> From<Error<E>> for ThermostatError<E> {
+    fn from(error: Error<E>) -> Self {
+        match error {
+            Error::I2C(e) => ThermostatError::I2C(e),
+        }
+    }
+}
+
+/// Error type for temperature controllers.
+#[derive(Debug)]
+pub enum ControlError<E, PE> {
+    /// Error reading the temperature.
+    ///
+    /// The output was set to the failsafe state.
+    Sensor(Error<E>),
+    /// Error setting the output.
+    Output(PE),
+}
+
+/// Conversion mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConversionMode {
+    /// Continuous conversion mode (default)
+    #[default]
+    Continuous,
+    /// One-shot conversion mode (shutdown)
+    OneShot,
+}
+
 /// Conversion rate for continuous conversion mode
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ConversionRate {
     /// 0.25Hz
     _0_25Hz,
@@ -274,6 +929,7 @@ pub enum ConversionRate {
 ///
 /// Number of consecutive faults necessary to trigger an alert.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum FaultQueue {
     /// 1 fault will trigger an alert (default)
     #[default]
@@ -288,6 +944,7 @@ pub enum FaultQueue {
 
 /// Alert polarity
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AlertPolarity {
     /// Active low (default)
     #[default]
@@ -298,6 +955,7 @@ pub enum AlertPolarity {
 
 /// Thermostat mode
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ThermostatMode {
     /// Comparator (default)
     ///
@@ -317,8 +975,111 @@ pub enum ThermostatMode {
     Interrupt,
 }
 
+/// Device driven by the alert pin when used as a standalone thermostat
+///
+/// See [`configure_as_thermostat()`](struct.Tmp1x2.html#method.configure_as_thermostat).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThermostatOutput {
+    /// The output is on while the temperature is low.
+    Heater,
+    /// The output is on while the temperature is high.
+    Cooler,
+}
+
+/// Desired device configuration
+///
+/// The conversion mode (shutdown bit) is not part of this configuration as
+/// it is determined by the driver type.
+///
+/// The default values correspond to the power-up (reset) configuration of
+/// the device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Configuration {
+    /// Extended measurement mode is enabled.
+    pub extended_mode: bool,
+    /// Conversion rate.
+    pub conversion_rate: ConversionRate,
+    /// Fault queue.
+    pub fault_queue: FaultQueue,
+    /// Alert polarity.
+    pub alert_polarity: AlertPolarity,
+    /// Thermostat mode.
+    pub thermostat_mode: ThermostatMode,
+    /// Low temperature threshold in degrees Celsius.
+    pub low_temperature_threshold: f32,
+    /// High temperature threshold in degrees Celsius.
+    pub high_temperature_threshold: f32,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Configuration {
+            extended_mode: false,
+            conversion_rate: ConversionRate::default(),
+            fault_queue: FaultQueue::default(),
+            alert_polarity: AlertPolarity::default(),
+            thermostat_mode: ThermostatMode::default(),
+            low_temperature_threshold: 75.0,
+            high_temperature_threshold: 80.0,
+        }
+    }
+}
+
+/// Expected and actual value of a setting that differs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Mismatch<T> {
+    /// Expected value.
+    pub expected: T,
+    /// Actual value in the device.
+    pub actual: T,
+}
+
+/// Differences between the desired and the actual device configuration
+///
+/// Each field is `None` if the setting matches.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigurationDiff {
+    /// Shutdown mode as expected from the conversion mode of the driver.
+    pub is_shutdown: Option<Mismatch<bool>>,
+    /// Extended measurement mode.
+    pub extended_mode: Option<Mismatch<bool>>,
+    /// Conversion rate.
+    pub conversion_rate: Option<Mismatch<ConversionRate>>,
+    /// Fault queue.
+    pub fault_queue: Option<Mismatch<FaultQueue>>,
+    /// Alert polarity.
+    pub alert_polarity: Option<Mismatch<AlertPolarity>>,
+    /// Thermostat mode.
+    pub thermostat_mode: Option<Mismatch<ThermostatMode>>,
+    /// Low temperature threshold in degrees Celsius.
+    pub low_temperature_threshold: Option<Mismatch<f32>>,
+    /// High temperature threshold in degrees Celsius.
+    pub high_temperature_threshold: Option<Mismatch<f32>>,
+}
+
+impl ConfigurationDiff {
+    /// Whether the device configuration matches the desired configuration.
+    pub fn is_empty(&self) -> bool {
+        *self == ConfigurationDiff::default()
+    }
+
+    fn config_register_differs(&self) -> bool {
+        self.is_shutdown.is_some()
+            || self.extended_mode.is_some()
+            || self.conversion_rate.is_some()
+            || self.fault_queue.is_some()
+            || self.alert_polarity.is_some()
+            || self.thermostat_mode.is_some()
+    }
+}
+
 /// Possible slave addresses
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SlaveAddr {
     /// Default slave address
     #[default]
@@ -338,15 +1099,6 @@ impl SlaveAddr {
 
 const DEVICE_BASE_ADDRESS: u8 = 0b100_1000;
 
-struct Register;
-
-impl Register {
-    const TEMPERATURE: u8 = 0x00;
-    const CONFIG: u8 = 0x01;
-    const T_LOW: u8 = 0x02;
-    const T_HIGH: u8 = 0x03;
-}
-
 struct BitFlagsHigh;
 
 impl BitFlagsHigh {
@@ -368,7 +1120,7 @@ impl BitFlagsLow {
     const CONV_RATE1: u8 = 0b1000_0000;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct RegisterU16 {
     lsb: u8,
     msb: u8,
@@ -376,6 +1128,21 @@ struct RegisterU16 {
 
 type Config = RegisterU16;
 
+impl From<RegisterU16> for u16 {
+    fn from(data: RegisterU16) -> Self {
+        u16::from(data.msb) << 8 | u16::from(data.lsb)
+    }
+}
+
+impl From<u16> for RegisterU16 {
+    fn from(value: u16) -> Self {
+        RegisterU16 {
+            msb: (value >> 8) as u8,
+            lsb: value as u8,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -420,6 +1187,37 @@ pub mod marker {
         #[derive(Debug)]
         pub struct OneShot(());
     }
+    pub mod range {
+        #[derive(Debug)]
+        pub struct Dynamic(());
+        #[derive(Debug)]
+        pub struct Normal(());
+        #[derive(Debug)]
+        pub struct Extended(());
+
+        /// Measurement range of the driver type.
+        pub trait MeasurementRange: private::Sealed {
+            /// Whether the range is extended, or `None` if it is not
+            /// tracked in the driver type.
+            const EXTENDED: Option<bool>;
+        }
+        impl MeasurementRange for Dynamic {
+            const EXTENDED: Option<bool> = None;
+        }
+        impl MeasurementRange for Normal {
+            const EXTENDED: Option<bool> = Some(false);
+        }
+        impl MeasurementRange for Extended {
+            const EXTENDED: Option<bool> = Some(true);
+        }
+
+        mod private {
+            pub trait Sealed {}
+            impl Sealed for super::Dynamic {}
+            impl Sealed for super::Normal {}
+            impl Sealed for super::Extended {}
+        }
+    }
 }
 
 /// TMP1X2 device driver.
@@ -432,16 +1230,21 @@ pub mod marker {
     async(feature = "async", keep_self)
 )]
 #[derive(Debug, Default)]
-pub struct Tmp1x2<I2C: AsyncI2c, MODE> {
+pub struct Tmp1x2<I2C: AsyncI2c, MODE, RANGE = marker::range::Dynamic> {
     /// The concrete I²C device implementation.
     i2c: I2C,
     /// The I²C device address.
     address: u8,
     /// Configuration register status.
     config: Config,
+    /// The cached configuration matches the device configuration.
+    config_is_known: bool,
+    /// Register the device pointer register currently points to, if known.
+    pointer: Option<Register>,
     /// A temperature conversion was started.
     a_temperature_conversion_was_started: bool,
     _mode: PhantomData<MODE>,
+    _range: PhantomData<RANGE>,
 }
 
 #[maybe_async_cfg::maybe(
@@ -464,8 +1267,11 @@ where
             i2c,
             address: address.addr(DEVICE_BASE_ADDRESS),
             config: Config::default(),
+            config_is_known: true,
+            pointer: None,
             a_temperature_conversion_was_started: false,
             _mode: PhantomData,
+            _range: PhantomData,
         }
     }
 }
@@ -478,7 +1284,7 @@ where
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E, MODE> Tmp1x2<I2C, MODE>
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
@@ -488,10 +1294,55 @@ where
     }
 }
 
+mod adaptive;
+pub use crate::adaptive::{AdaptiveSampler, AdaptiveSamplingSettings};
+mod aging;
+pub use crate::aging::{AgingAccumulator, AgingSettings, AgingState};
+mod alarm;
+pub use crate::alarm::{AlarmEvent, AlarmLevel, AlarmManager, AlarmSettings, AlarmState};
+mod borrowing;
+pub use crate::borrowing::{BorrowingTmp1x2, BoundTmp1x2};
+mod burst;
+pub use crate::burst::BurstStatistics;
 mod configuration;
 mod conversion;
+mod delta;
+pub use crate::delta::DeltaReporter;
+mod dynamic;
+pub use crate::dynamic::DynTmp1x2;
+mod filter;
+pub use crate::filter::{
+    ExponentialMovingAverage, Filter, FilteredReading, MedianFilter, MovingAverage,
+    OutlierRejection, SpikeRejection,
+};
+mod guard;
+pub use crate::guard::{GuardSettings, OverTemperatureGuard, ShutdownReason};
 mod interface;
+mod on_off;
+pub use crate::on_off::{OnOffController, OnOffSettings};
+mod pid;
+pub use crate::pid::{PidController, PidSettings};
+mod power;
+pub use crate::power::{PowerEstimate, PowerProfile};
+mod range;
+pub use crate::range::{ExtendedThreshold, NormalThreshold};
 mod reading;
+mod register;
+pub use crate::register::{
+    ConfigRegister, Register, RegisterDump, TemperatureRegister, WritableRegister,
+};
+mod sampling;
+pub use crate::sampling::{Clock, FreshSampleTracker};
+mod sink;
+pub use crate::sink::TemperatureSink;
+mod statistics;
+pub use crate::statistics::{StatisticsSnapshot, StatisticsTracker};
+mod stats;
+mod thermal_model;
+pub use crate::thermal_model::{SteadyStatePrediction, ThermalModel};
+mod timing;
+mod trend;
+pub use crate::trend::{Trend, TrendEstimator, TrendSettings};
 
 //impl<E> core::fmt::Debug for nb::Error<E> {}
 
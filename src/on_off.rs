@@ -0,0 +1,247 @@
+//! On/off (bang-bang) temperature control of a digital output.
+
+use crate::{marker::mode, Clock, ControlError, ThermostatOutput, Tmp1x2};
+use core::time::Duration;
+use embedded_hal::digital::OutputPin;
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Settings for [`OnOffController`](struct.OnOffController.html).
+///
+/// Temperatures are in degrees Celsius.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnOffSettings {
+    /// Target temperature.
+    pub setpoint: f32,
+    /// Width of the band around the setpoint in which the output keeps its
+    /// state.
+    ///
+    /// A heater is switched on below `setpoint` and off at or above
+    /// `setpoint + hysteresis`. A cooler is switched on at or above
+    /// `setpoint` and off below `setpoint - hysteresis`.
+    pub hysteresis: f32,
+    /// Whether the output drives a heater or a cooler.
+    pub output: ThermostatOutput,
+    /// Minimum time the output stays on before it can be switched off.
+    pub min_on_time: Duration,
+    /// Minimum time the output stays off before it can be switched on.
+    pub min_off_time: Duration,
+    /// Output state set when the temperature cannot be read.
+    pub failsafe_on: bool,
+}
+
+/// On/off controller driving a digital output from the temperature.
+///
+/// The output pin is set high to switch the heater or cooler on and low to
+/// switch it off. Call [`update()`](#method.update) periodically, for example
+/// once per conversion period.
+///
+/// The minimum on and off times protect relays and compressors from
+/// switching too often. They are measured with a monotonic
+/// [`Clock`](trait.Clock.html). The first update and the failsafe state
+/// ignore them.
+///
+/// If the temperature cannot be read, the output is set to the failsafe
+/// state and the error is returned.
+#[derive(Debug)]
+pub struct OnOffController<PIN, CLK> {
+    pin: PIN,
+    clock: CLK,
+    settings: OnOffSettings,
+    /// Current output state and time it was set.
+    state: Option<(bool, Duration)>,
+}
+
+impl<PIN, PE, CLK> OnOffController<PIN, CLK>
+where
+    PIN: OutputPin<Error = PE>,
+    CLK: Clock,
+{
+    /// Create a new controller.
+    ///
+    /// The pin is not changed until the first update.
+    pub fn new(pin: PIN, clock: CLK, settings: OnOffSettings) -> Self {
+        OnOffController {
+            pin,
+            clock,
+            settings,
+            state: None,
+        }
+    }
+
+    /// Destroy the controller, return the pin and clock.
+    pub fn destroy(self) -> (PIN, CLK) {
+        (self.pin, self.clock)
+    }
+
+    /// Current settings.
+    pub fn settings(&self) -> &OnOffSettings {
+        &self.settings
+    }
+
+    /// Change the settings.
+    ///
+    /// The new settings are used from the next update on.
+    pub fn set_settings(&mut self, settings: OnOffSettings) {
+        self.settings = settings;
+    }
+
+    /// Whether the output is on, if it has been set already.
+    pub fn is_on(&self) -> Option<bool> {
+        self.state.map(|(on, _)| on)
+    }
+
+    /// Update the output for the given temperature.
+    ///
+    /// Returns whether the output is on.
+    pub fn update_with_temperature(&mut self, temperature: f32) -> Result<bool, PE> {
+        let now = self.clock.now();
+        let on = match self.state {
+            None => self.wants_on(temperature, false),
+            Some((on, since)) => {
+                let min_time = if on {
+                    self.settings.min_on_time
+                } else {
+                    self.settings.min_off_time
+                };
+                if now.saturating_sub(since) < min_time {
+                    return Ok(on);
+                }
+                self.wants_on(temperature, on)
+            }
+        };
+        self.set_output(on, now)?;
+        Ok(on)
+    }
+
+    /// Set the output to the failsafe state.
+    pub fn apply_failsafe(&mut self) -> Result<(), PE> {
+        let now = self.clock.now();
+        self.set_output(self.settings.failsafe_on, now)
+    }
+
+    fn wants_on(&self, temperature: f32, on: bool) -> bool {
+        let OnOffSettings {
+            setpoint,
+            hysteresis,
+            ..
+        } = self.settings;
+        match (self.settings.output, on) {
+            (ThermostatOutput::Heater, false) => temperature < setpoint,
+            (ThermostatOutput::Heater, true) => temperature < setpoint + hysteresis,
+            (ThermostatOutput::Cooler, false) => temperature >= setpoint,
+            (ThermostatOutput::Cooler, true) => temperature >= setpoint - hysteresis,
+        }
+    }
+
+    fn set_output(&mut self, on: bool, now: Duration) -> Result<(), PE> {
+        if on {
+            self.pin.set_high()?;
+        } else {
+            self.pin.set_low()?;
+        }
+        match self.state {
+            Some((current, _)) if current == on => (),
+            _ => self.state = Some((on, now)),
+        }
+        Ok(())
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "OnOffController",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<PIN, PE, CLK> OnOffController<PIN, CLK>
+where
+    PIN: OutputPin<Error = PE>,
+    CLK: Clock,
+{
+    /// Read the temperature and update the output.
+    ///
+    /// If the temperature cannot be read, the output is set to the failsafe
+    /// state and the sensor error is returned. The sensor error is also
+    /// returned if setting the failsafe state fails.
+    ///
+    /// Returns whether the output is on.
+    pub async fn update<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<bool, ControlError<E, PE>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        match dev.read_temperature().await {
+            Ok(temperature) => self
+                .update_with_temperature(temperature)
+                .map_err(ControlError::Output),
+            Err(e) => {
+                let _ = self.apply_failsafe();
+                Err(ControlError::Sensor(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    struct Pin(bool);
+
+    impl embedded_hal::digital::ErrorType for Pin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for Pin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.0 = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.0 = true;
+            Ok(())
+        }
+    }
+
+    fn settings(output: ThermostatOutput) -> OnOffSettings {
+        OnOffSettings {
+            setpoint: 20.0,
+            hysteresis: 1.0,
+            output,
+            min_on_time: Duration::ZERO,
+            min_off_time: Duration::ZERO,
+            failsafe_on: false,
+        }
+    }
+
+    fn run(output: ThermostatOutput, temperatures: &[f32]) -> [bool; 5] {
+        let mut controller = OnOffController::new(Pin(false), || Duration::ZERO, settings(output));
+        let mut states = [false; 5];
+        for (state, temperature) in states.iter_mut().zip(temperatures) {
+            *state = controller.update_with_temperature(*temperature).unwrap();
+            assert_eq!(*state, controller.pin.0);
+        }
+        states
+    }
+
+    #[test]
+    fn heater_uses_hysteresis_above_setpoint() {
+        let states = run(ThermostatOutput::Heater, &[19.5, 20.5, 21.0, 20.5, 19.9]);
+        assert_eq!([true, true, false, false, true], states);
+    }
+
+    #[test]
+    fn cooler_uses_hysteresis_below_setpoint() {
+        let states = run(ThermostatOutput::Cooler, &[20.0, 19.5, 18.9, 19.5, 20.0]);
+        assert_eq!([true, true, false, false, true], states);
+    }
+}
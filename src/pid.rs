@@ -0,0 +1,313 @@
+//! PID temperature control of a PWM output.
+
+use crate::{marker::mode, ControlError, Error, ThermostatOutput, Tmp1x2};
+use core::time::Duration;
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+use embedded_hal::pwm::SetDutyCycle;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Settings for [`PidController`](struct.PidController.html).
+///
+/// Temperatures are in degrees Celsius, times in seconds and outputs are
+/// fractions of the maximum duty cycle between 0.0 and 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PidSettings {
+    /// Target temperature.
+    pub setpoint: f32,
+    /// Proportional gain in output per degree.
+    pub kp: f32,
+    /// Integral gain in output per degree and second.
+    pub ki: f32,
+    /// Derivative gain in output per degree per second.
+    pub kd: f32,
+    /// Whether the output drives a heater or a cooler.
+    ///
+    /// A heater output increases below the setpoint, a cooler output
+    /// increases above it.
+    pub output: ThermostatOutput,
+    /// Lowest output. Must not be greater than `output_max`.
+    pub output_min: f32,
+    /// Highest output.
+    pub output_max: f32,
+    /// Output set when the temperature cannot be read.
+    pub failsafe_output: f32,
+}
+
+/// PID controller driving a PWM output from the temperature.
+///
+/// The derivative term is calculated from the change of the measured
+/// temperature instead of the error, so that changing the setpoint does not
+/// cause an output spike. To avoid integral windup, the integral term is
+/// limited to the output range.
+///
+/// The controller is clocked by the measurements: in continuous mode the
+/// conversion period of the configured conversion rate is used as time step
+/// and [`update()`](#method.update) should be called once per conversion
+/// period. In one-shot mode
+/// [`update_one_shot()`](#method.update_one_shot) uses the interval between
+/// measurements given by the application.
+///
+/// If the temperature cannot be read, the output is set to the failsafe
+/// output and the error is returned. The controller state is kept.
+#[derive(Debug)]
+pub struct PidController<PWM> {
+    pwm: PWM,
+    settings: PidSettings,
+    integral: f32,
+    last_temperature: Option<f32>,
+    last_output: Option<f32>,
+}
+
+impl<PWM, PE> PidController<PWM>
+where
+    PWM: SetDutyCycle<Error = PE>,
+{
+    /// Create a new controller.
+    ///
+    /// The duty cycle is not changed until the first update.
+    pub fn new(pwm: PWM, settings: PidSettings) -> Self {
+        PidController {
+            pwm,
+            settings,
+            integral: 0.0,
+            last_temperature: None,
+            last_output: None,
+        }
+    }
+
+    /// Destroy the controller, return the PWM output.
+    pub fn destroy(self) -> PWM {
+        self.pwm
+    }
+
+    /// Current settings.
+    pub fn settings(&self) -> &PidSettings {
+        &self.settings
+    }
+
+    /// Change the settings.
+    ///
+    /// The controller state is kept, so the output does not jump when
+    /// the gains are tuned.
+    pub fn set_settings(&mut self, settings: PidSettings) {
+        self.settings = settings;
+    }
+
+    /// Clear the integral term and the previous measurement.
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_temperature = None;
+    }
+
+    /// Last output set, if any.
+    pub fn output(&self) -> Option<f32> {
+        self.last_output
+    }
+
+    /// Update the output for a temperature measured `dt` after the
+    /// previous one.
+    ///
+    /// The derivative term is 0 for the first measurement and if `dt` is 0.
+    /// Returns the output set.
+    pub fn update_with_temperature(&mut self, temperature: f32, dt: Duration) -> Result<f32, PE> {
+        let s = &self.settings;
+        let sign = match s.output {
+            ThermostatOutput::Heater => 1.0,
+            ThermostatOutput::Cooler => -1.0,
+        };
+        let dt = dt.as_secs_f32();
+        let error = sign * (s.setpoint - temperature);
+        let integral = self.integral + s.ki * error * dt;
+        let integral = integral.max(s.output_min).min(s.output_max);
+        let derivative = match self.last_temperature {
+            Some(last) if dt > 0.0 => -sign * s.kd * (temperature - last) / dt,
+            _ => 0.0,
+        };
+        let output = s.kp * error + integral + derivative;
+        let output = output.max(s.output_min).min(s.output_max);
+        self.set_output(output)?;
+        self.integral = integral;
+        self.last_temperature = Some(temperature);
+        Ok(output)
+    }
+
+    /// Set the output to the failsafe output.
+    pub fn apply_failsafe(&mut self) -> Result<(), PE> {
+        self.set_output(self.settings.failsafe_output)
+    }
+
+    fn set_output(&mut self, output: f32) -> Result<(), PE> {
+        let output = output.clamp(0.0, 1.0);
+        let max = self.pwm.max_duty_cycle();
+        let duty = libm::roundf(output * f32::from(max)) as u16;
+        self.pwm.set_duty_cycle(duty)?;
+        self.last_output = Some(output);
+        Ok(())
+    }
+
+    fn sensor_error<E>(&mut self, error: Error<E>) -> ControlError<E, PE> {
+        let _ = self.apply_failsafe();
+        ControlError::Sensor(error)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "PidController",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<PWM, PE> PidController<PWM>
+where
+    PWM: SetDutyCycle<Error = PE>,
+{
+    /// Read the temperature in continuous mode and update the output.
+    ///
+    /// The conversion period of the conversion rate configured through the
+    /// driver is used as time step. If the configuration cached in the driver
+    /// is unknown, it is read back from the device first.
+    ///
+    /// If the temperature cannot be read, the output is set to the failsafe
+    /// output and the sensor error is returned. The sensor error is also
+    /// returned if setting the failsafe output fails.
+    ///
+    /// Returns the output set.
+    pub async fn update<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Result<f32, ControlError<E, PE>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        let dt = match dev.conversion_rate().await {
+            Ok(rate) => rate.sample_period(),
+            Err(e) => return Err(self.sensor_error(e)),
+        };
+        match dev.read_temperature().await {
+            Ok(temperature) => self
+                .update_with_temperature(temperature, dt)
+                .map_err(ControlError::Output),
+            Err(e) => Err(self.sensor_error(e)),
+        }
+    }
+
+    /// Read a one-shot measurement and update the output.
+    ///
+    /// This triggers a measurement if none is in progress and returns
+    /// `nb::Error::WouldBlock` until it is finished, like
+    /// [`read_temperature()`](struct.Tmp1x2.html#method.read_temperature-1).
+    /// `interval` is the time between the measurements.
+    ///
+    /// If the temperature cannot be read, the output is set to the failsafe
+    /// output and the sensor error is returned.
+    ///
+    /// Returns the output set.
+    pub async fn update_one_shot<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::OneShot, RANGE>,
+        interval: Duration,
+    ) -> nb::Result<f32, ControlError<E, PE>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        match dev.read_temperature().await {
+            Ok(temperature) => self
+                .update_with_temperature(temperature, interval)
+                .map_err(|e| nb::Error::Other(ControlError::Output(e))),
+            Err(nb::Error::WouldBlock) => Err(nb::Error::WouldBlock),
+            Err(nb::Error::Other(e)) => Err(nb::Error::Other(self.sensor_error(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    struct Pwm(u16);
+
+    impl embedded_hal::pwm::ErrorType for Pwm {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for Pwm {
+        fn max_duty_cycle(&self) -> u16 {
+            1000
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+            self.0 = duty;
+            Ok(())
+        }
+    }
+
+    macro_rules! assert_near {
+        ($left:expr, $right:expr) => {
+            assert!(f32::abs($left - $right) < 1e-5);
+        };
+    }
+
+    fn settings(output: ThermostatOutput) -> PidSettings {
+        PidSettings {
+            setpoint: 20.0,
+            kp: 0.1,
+            ki: 0.01,
+            kd: 0.5,
+            output,
+            output_min: 0.0,
+            output_max: 1.0,
+            failsafe_output: 0.0,
+        }
+    }
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn heater_output_increases_below_setpoint() {
+        let mut pid = PidController::new(Pwm(0), settings(ThermostatOutput::Heater));
+        // P = 0.2, I = 0.02
+        assert_near!(0.22, pid.update_with_temperature(18.0, SECOND).unwrap());
+        assert_eq!(220, pid.pwm.0);
+        // P = 0.1, I = 0.03, D = -0.5
+        assert_near!(0.0, pid.update_with_temperature(19.0, SECOND).unwrap());
+        assert_eq!(0, pid.pwm.0);
+    }
+
+    #[test]
+    fn cooler_output_increases_above_setpoint() {
+        let mut pid = PidController::new(Pwm(0), settings(ThermostatOutput::Cooler));
+        assert_near!(0.22, pid.update_with_temperature(22.0, SECOND).unwrap());
+        assert_near!(0.0, pid.update_with_temperature(18.0, SECOND).unwrap());
+    }
+
+    #[test]
+    fn derivative_ignores_setpoint_changes() {
+        let mut s = settings(ThermostatOutput::Heater);
+        s.ki = 0.0;
+        let mut pid = PidController::new(Pwm(0), s);
+        pid.update_with_temperature(20.0, SECOND).unwrap();
+        s.setpoint = 25.0;
+        pid.set_settings(s);
+        assert_near!(0.5, pid.update_with_temperature(20.0, SECOND).unwrap());
+    }
+
+    #[test]
+    fn integral_is_limited_to_output_range() {
+        let mut s = settings(ThermostatOutput::Heater);
+        s.kp = 0.0;
+        s.kd = 0.0;
+        s.ki = 1.0;
+        s.output_max = 0.8;
+        let mut pid = PidController::new(Pwm(0), s);
+        for _ in 0..10 {
+            assert_near!(0.8, pid.update_with_temperature(10.0, SECOND).unwrap());
+        }
+        // Without windup the output decreases as soon as the error changes sign.
+        assert_near!(0.7, pid.update_with_temperature(20.1, SECOND).unwrap());
+    }
+}
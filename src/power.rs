@@ -0,0 +1,181 @@
+//! Supply current and energy estimation.
+
+use crate::{ConversionMode, ConversionRate};
+use core::time::Duration;
+
+/// Number of bits transferred on the bus per byte, including the
+/// acknowledge bit.
+const BITS_PER_BYTE: f32 = 9.0;
+
+/// Bytes transferred per reading in continuous conversion mode.
+///
+/// The pointer register already points to the temperature register, so only
+/// the address and the two data bytes are transferred.
+const CONTINUOUS_READING_BYTES: f32 = 3.0;
+
+/// Bytes transferred per reading in one-shot conversion mode.
+///
+/// Triggering the conversion writes the configuration register (4 bytes),
+/// reading it back once (3 bytes) and reading the temperature register
+/// including the pointer (5 bytes).
+const ONE_SHOT_READING_BYTES: f32 = 12.0;
+
+/// Supply current characteristics of a device.
+///
+/// All currents are in microamperes. The predefined profiles use the typical
+/// datasheet values at 25°C. Other values can be used by building a profile
+/// directly.
+///
+/// Assumptions:
+/// - Between conversions in continuous mode the device draws the shutdown
+///   current.
+/// - The current during a conversion is not given in the datasheets. It is
+///   derived from the typical average quiescent current of 10 µA at the
+///   default 4 Hz conversion rate and the typical conversion time of 26 ms.
+/// - The additional current while the bus is active is interpolated linearly
+///   from zero through the datasheet values at 400 kHz and 3.4 MHz. It applies
+///   while the bytes for a reading are transferred.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerProfile {
+    /// Current during a conversion.
+    pub conversion_current: f32,
+    /// Current in shutdown and between conversions.
+    pub shutdown_current: f32,
+    /// Additional current while the bus is active at 400 kHz.
+    pub bus_active_current_400khz: f32,
+    /// Additional current while the bus is active at 3.4 MHz.
+    pub bus_active_current_3_4mhz: f32,
+    /// Supply voltage in volts used for the energy estimation.
+    pub supply_voltage: f32,
+}
+
+impl PowerProfile {
+    /// TMP102 at 3.3 V.
+    ///
+    /// Typical values: 10 µA average quiescent current at 4 Hz, 0.5 µA
+    /// shutdown current, 15 µA and 85 µA quiescent current with the bus
+    /// active at 400 kHz and 3.4 MHz.
+    pub const TMP102: PowerProfile = PowerProfile {
+        conversion_current: 91.85,
+        shutdown_current: 0.5,
+        bus_active_current_400khz: 5.0,
+        bus_active_current_3_4mhz: 75.0,
+        supply_voltage: 3.3,
+    };
+
+    /// TMP112 family at 3.3 V.
+    ///
+    /// The TMP112 datasheet specifies the same typical supply currents as
+    /// the TMP102. Its higher accuracy does not affect this estimation.
+    pub const TMP112: PowerProfile = PowerProfile::TMP102;
+
+    /// Estimate the average supply current and the energy per reading.
+    ///
+    /// One reading is done per conversion, that is, once per sample period
+    /// of `rate` in continuous mode and once per `one_shot_period` in
+    /// one-shot mode. `rate` is ignored in one-shot mode and
+    /// `one_shot_period` in continuous mode. A one-shot period shorter than
+    /// the conversion time is treated as back-to-back conversions.
+    pub fn estimate(
+        &self,
+        mode: ConversionMode,
+        rate: ConversionRate,
+        one_shot_period: Duration,
+        bus_frequency_hz: u32,
+    ) -> PowerEstimate {
+        let conversion_time = rate.typical_conversion_time();
+        let (period, bytes) = match mode {
+            ConversionMode::Continuous => (rate.sample_period(), CONTINUOUS_READING_BYTES),
+            ConversionMode::OneShot => {
+                (one_shot_period.max(conversion_time), ONE_SHOT_READING_BYTES)
+            }
+        };
+        let period = period.as_secs_f32();
+        let conversion_time = conversion_time.as_secs_f32();
+        let bus_time = if bus_frequency_hz == 0 {
+            0.0
+        } else {
+            bytes * BITS_PER_BYTE / bus_frequency_hz as f32
+        };
+        let charge = self.conversion_current * conversion_time
+            + self.shutdown_current * (period - conversion_time)
+            + self.bus_active_current(bus_frequency_hz) * bus_time;
+        PowerEstimate {
+            average_current: charge / period,
+            energy_per_reading: charge * self.supply_voltage,
+        }
+    }
+
+    fn bus_active_current(&self, bus_frequency_hz: u32) -> f32 {
+        let f = bus_frequency_hz as f32;
+        if f <= 400_000.0 {
+            self.bus_active_current_400khz * f / 400_000.0
+        } else {
+            let slope = (self.bus_active_current_3_4mhz - self.bus_active_current_400khz)
+                / (3_400_000.0 - 400_000.0);
+            self.bus_active_current_400khz + slope * (f - 400_000.0)
+        }
+    }
+}
+
+/// Result of a supply current estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerEstimate {
+    /// Average supply current in microamperes.
+    pub average_current: f32,
+    /// Energy per reading in microjoules.
+    pub energy_per_reading: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(expected: f32, actual: f32, tolerance: f32) {
+        assert!(
+            f32::abs(expected - actual) < tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn matches_datasheet_average_at_default_rate() {
+        let estimate = PowerProfile::TMP102.estimate(
+            ConversionMode::Continuous,
+            ConversionRate::_4Hz,
+            Duration::ZERO,
+            400_000,
+        );
+        assert_near(10.0, estimate.average_current, 0.01);
+        assert_near(10.0 * 3.3 * 0.25, estimate.energy_per_reading, 0.01);
+    }
+
+    #[test]
+    fn one_shot_uses_less_current_at_long_periods() {
+        let profile = PowerProfile::TMP112;
+        let continuous = profile.estimate(
+            ConversionMode::Continuous,
+            ConversionRate::_0_25Hz,
+            Duration::ZERO,
+            100_000,
+        );
+        let one_shot = profile.estimate(
+            ConversionMode::OneShot,
+            ConversionRate::_0_25Hz,
+            Duration::from_secs(60),
+            100_000,
+        );
+        assert!(one_shot.average_current < continuous.average_current);
+        assert_near(0.54, one_shot.average_current, 0.01);
+    }
+
+    #[test]
+    fn bus_speed_increases_bus_current() {
+        let profile = PowerProfile::TMP102;
+        assert_near(0.0, profile.bus_active_current(0), 1e-6);
+        assert_near(5.0, profile.bus_active_current(400_000), 1e-6);
+        assert_near(75.0, profile.bus_active_current(3_400_000), 1e-4);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{
+    marker::range::{self, MeasurementRange},
+    BitFlagsLow as BFL, Error, ModeChangeError, Register, RegisterU16, Tmp1x2,
+};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Temperature threshold for a device in normal measurement range.
+///
+/// The value is in the interval `[-128.0, 127.9375]`°C with a resolution
+/// of 0.0625°C.
+///
+/// Thresholds built in a constant context are checked at compile time:
+///
+/// ```compile_fail
+/// use tmp1x2::NormalThreshold;
+///
+/// const TOO_HOT: NormalThreshold = NormalThreshold::from_celsius(200);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NormalThreshold(i16);
+
+impl NormalThreshold {
+    /// Create a threshold from a whole number of degrees Celsius.
+    ///
+    /// In a constant context, a value outside of `[-128, 127]` is a compile
+    /// error. Use [`try_from_celsius()`](#method.try_from_celsius) for values
+    /// only known at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics at runtime if the value is outside of `[-128, 127]`.
+    pub const fn from_celsius(celsius: i16) -> Self {
+        match Self::try_from_celsius(celsius) {
+            Some(threshold) => threshold,
+            None => panic!("threshold out of normal range"),
+        }
+    }
+
+    /// Create a threshold from a whole number of degrees Celsius.
+    ///
+    /// Returns `None` if the value is outside of `[-128, 127]`.
+    pub const fn try_from_celsius(celsius: i16) -> Option<Self> {
+        if celsius >= -128 && celsius <= 127 {
+            Some(NormalThreshold(celsius * 16))
+        } else {
+            None
+        }
+    }
+
+    /// Create a threshold from a temperature in degrees Celsius.
+    ///
+    /// The value is truncated to a multiple of 0.0625°C.
+    /// Returns `None` if the value is outside of `[-128.0, 127.9375]`.
+    pub fn new(celsius: f32) -> Option<Self> {
+        if (-128.0..=127.9375).contains(&celsius) {
+            Some(NormalThreshold((celsius / 0.0625) as i16))
+        } else {
+            None
+        }
+    }
+
+    /// Temperature in degrees Celsius.
+    pub fn celsius(self) -> f32 {
+        f32::from(self.0) * 0.0625
+    }
+
+    /// Create a threshold, limiting the value to the normal range.
+    fn saturating(celsius: f32) -> Self {
+        NormalThreshold((celsius.clamp(-128.0, 127.9375) / 0.0625) as i16)
+    }
+
+    fn to_register(self) -> RegisterU16 {
+        RegisterU16::from((self.0 << 4) as u16)
+    }
+}
+
+/// Temperature threshold for a device in extended measurement range.
+///
+/// The value is in the interval `[-256.0, 255.875]`°C with a resolution
+/// of 0.0625°C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ExtendedThreshold(i16);
+
+impl ExtendedThreshold {
+    /// Create a threshold from a whole number of degrees Celsius.
+    ///
+    /// In a constant context, a value outside of `[-256, 255]` is a compile
+    /// error. Use [`try_from_celsius()`](#method.try_from_celsius) for values
+    /// only known at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics at runtime if the value is outside of `[-256, 255]`.
+    pub const fn from_celsius(celsius: i16) -> Self {
+        match Self::try_from_celsius(celsius) {
+            Some(threshold) => threshold,
+            None => panic!("threshold out of extended range"),
+        }
+    }
+
+    /// Create a threshold from a whole number of degrees Celsius.
+    ///
+    /// Returns `None` if the value is outside of `[-256, 255]`.
+    pub const fn try_from_celsius(celsius: i16) -> Option<Self> {
+        if celsius >= -256 && celsius <= 255 {
+            Some(ExtendedThreshold(celsius * 16))
+        } else {
+            None
+        }
+    }
+
+    /// Create a threshold from a temperature in degrees Celsius.
+    ///
+    /// The value is truncated to a multiple of 0.0625°C.
+    /// Returns `None` if the value is outside of `[-256.0, 255.875]`.
+    pub fn new(celsius: f32) -> Option<Self> {
+        if (-256.0..=255.875).contains(&celsius) {
+            Some(ExtendedThreshold((celsius / 0.0625) as i16))
+        } else {
+            None
+        }
+    }
+
+    /// Temperature in degrees Celsius.
+    pub fn celsius(self) -> f32 {
+        f32::from(self.0) * 0.0625
+    }
+
+    /// Create a threshold, limiting the value to the extended range.
+    fn saturating(celsius: f32) -> Self {
+        ExtendedThreshold((celsius.clamp(-256.0, 255.875) / 0.0625) as i16)
+    }
+
+    fn to_register(self) -> RegisterU16 {
+        RegisterU16::from((self.0 << 3) as u16)
+    }
+}
+
+impl From<NormalThreshold> for ExtendedThreshold {
+    fn from(threshold: NormalThreshold) -> Self {
+        ExtendedThreshold(threshold.0)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Change into normal measurement range.
+    ///
+    /// This disables the extended measurement mode. The device will not
+    /// measure temperatures above 128°C.
+    ///
+    /// If the range change failed you will get a `ModeChangeError`.
+    /// You can get the unchanged device back from it.
+    pub async fn into_normal(
+        mut self,
+    ) -> Result<Tmp1x2<I2C, MODE, range::Normal>, ModeChangeError<E, Self>> {
+        if let Err(Error::I2C(e)) = self.write_extended_mode(false).await {
+            return Err(ModeChangeError::I2C(e, self));
+        }
+        Ok(self.with_range())
+    }
+
+    /// Change into extended measurement range.
+    ///
+    /// This enables the extended measurement mode, which allows measurement
+    /// of temperatures above 128°C.
+    ///
+    /// If the range change failed you will get a `ModeChangeError`.
+    /// You can get the unchanged device back from it.
+    pub async fn into_extended(
+        mut self,
+    ) -> Result<Tmp1x2<I2C, MODE, range::Extended>, ModeChangeError<E, Self>> {
+        if let Err(Error::I2C(e)) = self.write_extended_mode(true).await {
+            return Err(ModeChangeError::I2C(e, self));
+        }
+        Ok(self.with_range())
+    }
+
+    /// Stop tracking the measurement range in the driver type.
+    ///
+    /// This does not alter the device configuration.
+    pub fn into_dynamic_range(self) -> Tmp1x2<I2C, MODE, range::Dynamic> {
+        self.with_range()
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE> Tmp1x2<I2C, MODE, range::Normal>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Set the high temperature threshold.
+    pub async fn set_high_temperature_threshold(
+        &mut self,
+        threshold: NormalThreshold,
+    ) -> Result<(), Error<E>> {
+        self.write_register(Register::THigh, threshold.to_register())
+            .await
+    }
+
+    /// Set the low temperature threshold.
+    pub async fn set_low_temperature_threshold(
+        &mut self,
+        threshold: NormalThreshold,
+    ) -> Result<(), Error<E>> {
+        self.write_register(Register::TLow, threshold.to_register())
+            .await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE> Tmp1x2<I2C, MODE, range::Extended>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Set the high temperature threshold.
+    pub async fn set_high_temperature_threshold(
+        &mut self,
+        threshold: ExtendedThreshold,
+    ) -> Result<(), Error<E>> {
+        self.write_register(Register::THigh, threshold.to_register())
+            .await
+    }
+
+    /// Set the low temperature threshold.
+    pub async fn set_low_temperature_threshold(
+        &mut self,
+        threshold: ExtendedThreshold,
+    ) -> Result<(), Error<E>> {
+        self.write_register(Register::TLow, threshold.to_register())
+            .await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+    RANGE: MeasurementRange,
+{
+    /// Set a threshold through the `NormalThreshold` or `ExtendedThreshold`
+    /// of the measurement range, limiting the value to the range.
+    ///
+    /// If the range is not tracked in the driver type, it is taken from the
+    /// configuration.
+    pub(crate) async fn set_threshold_saturating(
+        &mut self,
+        temperature: f32,
+        register: Register,
+    ) -> Result<(), Error<E>> {
+        let extended = match RANGE::EXTENDED {
+            Some(extended) => extended,
+            None => (self.current_config().await?.lsb & BFL::EXTENDED_MODE) != 0,
+        };
+        let data = if extended {
+            ExtendedThreshold::saturating(temperature).to_register()
+        } else {
+            NormalThreshold::saturating(temperature).to_register()
+        };
+        self.write_register(register, data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtendedThreshold, NormalThreshold};
+
+    #[test]
+    fn can_create_normal_threshold() {
+        assert_eq!(
+            NormalThreshold::new(127.9375),
+            Some(NormalThreshold(127 * 16 + 15))
+        );
+        assert_eq!(
+            NormalThreshold::new(-128.0),
+            Some(NormalThreshold::from_celsius(-128))
+        );
+        assert_eq!(NormalThreshold::new(128.0), None);
+        assert_eq!(NormalThreshold::new(-128.0625), None);
+    }
+
+    #[test]
+    fn can_create_extended_threshold() {
+        assert_eq!(
+            ExtendedThreshold::new(200.5),
+            Some(ExtendedThreshold(200 * 16 + 8))
+        );
+        assert_eq!(ExtendedThreshold::new(256.0), None);
+        assert_eq!(ExtendedThreshold::new(-256.0625), None);
+    }
+
+    #[test]
+    fn can_try_to_create_threshold_from_celsius() {
+        assert_eq!(
+            NormalThreshold::try_from_celsius(127),
+            Some(NormalThreshold::from_celsius(127))
+        );
+        assert_eq!(NormalThreshold::try_from_celsius(128), None);
+        assert_eq!(
+            ExtendedThreshold::try_from_celsius(-256),
+            Some(ExtendedThreshold::from_celsius(-256))
+        );
+        assert_eq!(ExtendedThreshold::try_from_celsius(256), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn normal_threshold_out_of_range_panics() {
+        let celsius = 200;
+        NormalThreshold::from_celsius(celsius);
+    }
+
+    #[test]
+    fn saturating_thresholds_are_limited_to_range() {
+        assert_eq!(
+            NormalThreshold::saturating(130.0),
+            NormalThreshold::new(127.9375).unwrap()
+        );
+        assert_eq!(
+            ExtendedThreshold::saturating(-300.0),
+            ExtendedThreshold::from_celsius(-256)
+        );
+        assert_eq!(
+            NormalThreshold::saturating(25.5),
+            NormalThreshold::new(25.5).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_encode_thresholds() {
+        assert_eq!(
+            u16::from(NormalThreshold::from_celsius(-25).to_register()),
+            0xE700
+        );
+        assert_eq!(
+            u16::from(ExtendedThreshold::from_celsius(150).to_register()),
+            0x4B00
+        );
+    }
+}
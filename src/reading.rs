@@ -1,4 +1,4 @@
-use crate::conversion::convert_temp_from_register;
+use crate::conversion::{convert_temp_from_register, convert_temp_from_register_msb};
 use crate::{marker::mode, BitFlagsHigh, BitFlagsLow, Error, Register, Tmp1x2};
 #[cfg(not(feature = "async"))]
 use embedded_hal::i2c::I2c;
@@ -13,15 +13,24 @@ use embedded_hal_async::i2c::I2c as AsyncI2c;
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E> Tmp1x2<I2C, mode::Continuous>
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::Continuous, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
     /// Read the temperature from the sensor.
     pub async fn read_temperature(&mut self) -> Result<f32, Error<E>> {
-        let data = self.read_register_u16(Register::TEMPERATURE).await?;
+        let data = self.read_register_u16(Register::Temperature).await?;
         Ok(convert_temp_from_register(data.msb, data.lsb))
     }
+
+    /// Read the temperature from the sensor in whole degrees Celsius.
+    ///
+    /// Only the most significant byte of the temperature register is
+    /// transferred. The value is rounded down to 1°C resolution in normal
+    /// mode and to 2°C resolution in extended mode.
+    pub async fn read_temperature_coarse(&mut self) -> Result<i16, Error<E>> {
+        self.read_temperature_msb().await
+    }
 }
 
 #[maybe_async_cfg::maybe(
@@ -32,13 +41,13 @@ where
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E> Tmp1x2<I2C, mode::OneShot>
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::OneShot, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
     /// Read whether the one-shot measurement result is ready.
     async fn one_shot_measurement_is_ready(&mut self) -> Result<bool, Error<E>> {
-        let config = self.read_register_u16(Register::CONFIG).await?;
+        let config = self.read_register_u16(Register::Config).await?;
         Ok((config.msb & BitFlagsHigh::ONE_SHOT) != 0)
     }
 
@@ -55,6 +64,35 @@ where
     /// temperature measurement is finished. Then it will return the
     /// measured temperature.
     pub async fn read_temperature(&mut self) -> nb::Result<f32, Error<E>> {
+        self.wait_for_one_shot_measurement().await?;
+        let data = self
+            .read_register_u16(Register::Temperature)
+            .await
+            .map_err(nb::Error::Other)?;
+        self.a_temperature_conversion_was_started = false;
+        Ok(convert_temp_from_register(data.msb, data.lsb))
+    }
+
+    /// Perform a one-shot temperature measurement and read the result in
+    /// whole degrees Celsius.
+    ///
+    /// This behaves like [`read_temperature()`](#method.read_temperature)
+    /// but only the most significant byte of the temperature register is
+    /// transferred. The value is rounded down to 1°C resolution in normal
+    /// mode and to 2°C resolution in extended mode.
+    pub async fn read_temperature_coarse(&mut self) -> nb::Result<i16, Error<E>> {
+        self.wait_for_one_shot_measurement().await?;
+        let temp = self
+            .read_temperature_msb()
+            .await
+            .map_err(nb::Error::Other)?;
+        self.a_temperature_conversion_was_started = false;
+        Ok(temp)
+    }
+
+    /// Start a one-shot measurement if none was started yet and return
+    /// `nb::Error::WouldBlock` until its result is ready.
+    async fn wait_for_one_shot_measurement(&mut self) -> nb::Result<(), Error<E>> {
         if !self.a_temperature_conversion_was_started {
             self.trigger_one_shot_measurement()
                 .await
@@ -62,20 +100,14 @@ where
             self.a_temperature_conversion_was_started = true;
             return Err(nb::Error::WouldBlock);
         }
-        if !self
+        if self
             .one_shot_measurement_is_ready()
             .await
             .map_err(nb::Error::Other)?
         {
-            Err(nb::Error::WouldBlock)
+            Ok(())
         } else {
-            let data = self
-                .read_register_u16(Register::TEMPERATURE)
-                .await
-                .map_err(nb::Error::Other)?;
-            let temp = convert_temp_from_register(data.msb, data.lsb);
-            self.a_temperature_conversion_was_started = false;
-            Ok(temp)
+            Err(nb::Error::WouldBlock)
         }
     }
 }
@@ -88,7 +120,7 @@ where
     ),
     async(feature = "async", keep_self)
 )]
-impl<I2C, E, MODE> Tmp1x2<I2C, MODE>
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
 where
     I2C: AsyncI2c<Error = E>,
 {
@@ -103,9 +135,15 @@ where
     /// [AlertPolarity](enum.AlertPolarity.html).
     #[allow(clippy::wrong_self_convention)]
     pub async fn is_comparator_mode_alert_active(&mut self) -> Result<bool, Error<E>> {
-        let config = self.read_register_u16(Register::CONFIG).await?;
+        let config = self.read_register_u16(Register::Config).await?;
         let is_alert_polarity_high = (config.msb & BitFlagsHigh::ALERT_POLARITY) != 0;
         let alert_status = (config.lsb & BitFlagsLow::ALERT) != 0;
         Ok(is_alert_polarity_high == alert_status)
     }
+
+    async fn read_temperature_msb(&mut self) -> Result<i16, Error<E>> {
+        let msb = self.read_register_msb(Register::Temperature).await?;
+        let extended_mode = (self.current_config().await?.lsb & BitFlagsLow::EXTENDED_MODE) != 0;
+        Ok(convert_temp_from_register_msb(msb, extended_mode))
+    }
 }
@@ -0,0 +1,425 @@
+//! Low-level register access types.
+
+use crate::conversion::{convert_temp_from_register, convert_temp_from_register_in_mode};
+use crate::{
+    AlertPolarity, BitFlagsHigh as BFH, BitFlagsLow as BFL, ConversionRate, Error, FaultQueue,
+    RegisterU16, ThermostatMode, Tmp1x2,
+};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Device registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Register {
+    /// Temperature register (read-only)
+    Temperature = 0x00,
+    /// Configuration register
+    Config = 0x01,
+    /// Low temperature threshold register (`T_LOW`)
+    TLow = 0x02,
+    /// High temperature threshold register (`T_HIGH`)
+    THigh = 0x03,
+}
+
+impl Register {
+    pub(crate) fn addr(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Writable device registers
+///
+/// See [`write_register_raw()`](struct.Tmp1x2.html#method.write_register_raw).
+/// The temperature register is read-only:
+///
+/// ```compile_fail
+/// use tmp1x2::WritableRegister;
+///
+/// let register = WritableRegister::Temperature;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WritableRegister {
+    /// Configuration register
+    Config,
+    /// Low temperature threshold register (`T_LOW`)
+    TLow,
+    /// High temperature threshold register (`T_HIGH`)
+    THigh,
+}
+
+impl From<WritableRegister> for Register {
+    fn from(register: WritableRegister) -> Self {
+        match register {
+            WritableRegister::Config => Register::Config,
+            WritableRegister::TLow => Register::TLow,
+            WritableRegister::THigh => Register::THigh,
+        }
+    }
+}
+
+/// Decoded view of a raw configuration register value.
+///
+/// The raw value contains the first transmitted byte (MSB) in the upper
+/// 8 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigRegister(u16);
+
+impl Default for ConfigRegister {
+    /// Power-up (reset) configuration of the device.
+    fn default() -> Self {
+        ConfigRegister::from(RegisterU16::default())
+    }
+}
+
+impl ConfigRegister {
+    /// Create a view of a raw configuration register value.
+    pub const fn from_bits(bits: u16) -> Self {
+        ConfigRegister(bits)
+    }
+
+    /// Raw configuration register value.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    fn msb(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    fn lsb(self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Whether the shutdown mode (SD bit) is enabled.
+    pub fn is_shutdown(self) -> bool {
+        (self.msb() & BFH::SHUTDOWN) != 0
+    }
+
+    /// Thermostat mode (TM bit).
+    pub fn thermostat_mode(self) -> ThermostatMode {
+        if (self.msb() & BFH::THERMOSTAT) != 0 {
+            ThermostatMode::Interrupt
+        } else {
+            ThermostatMode::Comparator
+        }
+    }
+
+    /// Alert polarity (POL bit).
+    pub fn alert_polarity(self) -> AlertPolarity {
+        if (self.msb() & BFH::ALERT_POLARITY) != 0 {
+            AlertPolarity::ActiveHigh
+        } else {
+            AlertPolarity::ActiveLow
+        }
+    }
+
+    /// Fault queue (F1 and F0 bits).
+    pub fn fault_queue(self) -> FaultQueue {
+        let msb = self.msb();
+        match (msb & BFH::FAULT_QUEUE1 != 0, msb & BFH::FAULT_QUEUE0 != 0) {
+            (false, false) => FaultQueue::_1,
+            (false, true) => FaultQueue::_2,
+            (true, false) => FaultQueue::_4,
+            (true, true) => FaultQueue::_6,
+        }
+    }
+
+    /// Converter resolution bits (R1 and R0).
+    ///
+    /// These are read-only and read as `0b11` (12 bits) on the device.
+    pub fn resolution_bits(self) -> u8 {
+        (self.msb() & BFH::RESOLUTION) >> 5
+    }
+
+    /// One-shot conversion bit (OS).
+    ///
+    /// When read in shutdown mode this is set once a one-shot conversion
+    /// is finished.
+    pub fn one_shot_bit(self) -> bool {
+        (self.msb() & BFH::ONE_SHOT) != 0
+    }
+
+    /// Whether the extended measurement mode (EM bit) is enabled.
+    pub fn is_extended_mode(self) -> bool {
+        (self.lsb() & BFL::EXTENDED_MODE) != 0
+    }
+
+    /// Alert bit (AL).
+    ///
+    /// Its meaning depends on the alert polarity. See
+    /// [`is_comparator_mode_alert_active()`](struct.Tmp1x2.html#method.is_comparator_mode_alert_active).
+    pub fn alert_bit(self) -> bool {
+        (self.lsb() & BFL::ALERT) != 0
+    }
+
+    /// Conversion rate (CR1 and CR0 bits).
+    pub fn conversion_rate(self) -> ConversionRate {
+        let lsb = self.lsb();
+        match (lsb & BFL::CONV_RATE1 != 0, lsb & BFL::CONV_RATE0 != 0) {
+            (false, false) => ConversionRate::_0_25Hz,
+            (false, true) => ConversionRate::_1Hz,
+            (true, false) => ConversionRate::_4Hz,
+            (true, true) => ConversionRate::_8Hz,
+        }
+    }
+}
+
+impl From<RegisterU16> for ConfigRegister {
+    fn from(data: RegisterU16) -> Self {
+        ConfigRegister(data.into())
+    }
+}
+
+/// Decoded view of a raw temperature register value.
+///
+/// The raw value contains the first transmitted byte (MSB) in the upper
+/// 8 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TemperatureRegister(u16);
+
+impl TemperatureRegister {
+    /// Create a view of a raw temperature register value.
+    pub const fn from_bits(bits: u16) -> Self {
+        TemperatureRegister(bits)
+    }
+
+    /// Raw temperature register value.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Whether the value was measured in extended mode (EM bit).
+    ///
+    /// In extended mode the temperature is a 13-bit value. Otherwise it is
+    /// a 12-bit value.
+    pub fn is_extended(self) -> bool {
+        (self.0 & 1) != 0
+    }
+
+    /// Temperature in degrees Celsius.
+    pub fn celsius(self) -> f32 {
+        convert_temp_from_register((self.0 >> 8) as u8, self.0 as u8)
+    }
+}
+
+/// Decoded contents of all device registers.
+///
+/// See [`dump()`](struct.Tmp1x2.html#method.dump).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterDump {
+    /// Temperature in degrees Celsius.
+    pub temperature: f32,
+    /// Shutdown mode (SD bit) is enabled.
+    pub is_shutdown: bool,
+    /// One-shot conversion bit (OS).
+    pub one_shot_bit: bool,
+    /// Thermostat mode (TM bit).
+    pub thermostat_mode: ThermostatMode,
+    /// Alert polarity (POL bit).
+    pub alert_polarity: AlertPolarity,
+    /// Fault queue (F1 and F0 bits).
+    pub fault_queue: FaultQueue,
+    /// Converter resolution bits (R1 and R0).
+    pub resolution_bits: u8,
+    /// Extended measurement mode (EM bit) is enabled.
+    pub is_extended_mode: bool,
+    /// Alert bit (AL).
+    pub alert_bit: bool,
+    /// Conversion rate (CR1 and CR0 bits).
+    pub conversion_rate: ConversionRate,
+    /// Low temperature threshold in degrees Celsius.
+    pub low_temperature_threshold: f32,
+    /// High temperature threshold in degrees Celsius.
+    pub high_temperature_threshold: f32,
+}
+
+impl core::fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let enabled = |value| if value { "enabled" } else { "disabled" };
+        let thermostat_mode = match self.thermostat_mode {
+            ThermostatMode::Comparator => "comparator",
+            ThermostatMode::Interrupt => "interrupt",
+        };
+        let alert_polarity = match self.alert_polarity {
+            AlertPolarity::ActiveLow => "active low",
+            AlertPolarity::ActiveHigh => "active high",
+        };
+        let fault_queue = match self.fault_queue {
+            FaultQueue::_1 => 1,
+            FaultQueue::_2 => 2,
+            FaultQueue::_4 => 4,
+            FaultQueue::_6 => 6,
+        };
+        let conversion_rate = match self.conversion_rate {
+            ConversionRate::_0_25Hz => "0.25Hz",
+            ConversionRate::_1Hz => "1Hz",
+            ConversionRate::_4Hz => "4Hz",
+            ConversionRate::_8Hz => "8Hz",
+        };
+        writeln!(f, "Temperature: {:.4}°C", self.temperature)?;
+        writeln!(f, "Shutdown mode: {}", enabled(self.is_shutdown))?;
+        writeln!(f, "One-shot bit: {}", u8::from(self.one_shot_bit))?;
+        writeln!(f, "Thermostat mode: {}", thermostat_mode)?;
+        writeln!(f, "Alert polarity: {}", alert_polarity)?;
+        writeln!(f, "Fault queue: {} consecutive fault(s)", fault_queue)?;
+        writeln!(f, "Resolution bits: {:#04b}", self.resolution_bits)?;
+        writeln!(f, "Extended mode: {}", enabled(self.is_extended_mode))?;
+        writeln!(f, "Alert bit: {}", u8::from(self.alert_bit))?;
+        writeln!(f, "Conversion rate: {}", conversion_rate)?;
+        writeln!(
+            f,
+            "Low temperature threshold: {:.4}°C",
+            self.low_temperature_threshold
+        )?;
+        write!(
+            f,
+            "High temperature threshold: {:.4}°C",
+            self.high_temperature_threshold
+        )
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Read the raw value of a register.
+    ///
+    /// *Low-level API:* This is intended for diagnostics. The first
+    /// transmitted byte (MSB) is placed in the upper 8 bits.
+    ///
+    /// See also: [ConfigRegister](struct.ConfigRegister.html),
+    /// [TemperatureRegister](struct.TemperatureRegister.html).
+    pub async fn read_register_raw(&mut self, register: Register) -> Result<u16, Error<E>> {
+        let data = self.read_register_u16(register).await?;
+        Ok(data.into())
+    }
+
+    /// Write the raw value of a register.
+    ///
+    /// *Low-level API:* This is intended for diagnostics. The upper 8 bits
+    /// are transmitted first (MSB).
+    ///
+    /// Values written to the configuration register are stored as the
+    /// cached configuration of this driver. Changing the shutdown bit
+    /// or extended mode bit this way is not reflected in the conversion
+    /// mode or measurement range of the driver type, though.
+    pub async fn write_register_raw(
+        &mut self,
+        register: WritableRegister,
+        value: u16,
+    ) -> Result<(), Error<E>> {
+        let register = Register::from(register);
+        let data = RegisterU16::from(value);
+        if register == Register::Config {
+            // The one-shot bit is not stored
+            self.write_register(register, data.clone()).await?;
+            self.config = data.with_low_msb(BFH::ONE_SHOT);
+            self.config_is_known = true;
+            Ok(())
+        } else {
+            self.write_register(register, data).await
+        }
+    }
+
+    /// Read all registers and decode their contents.
+    ///
+    /// The thresholds are decoded according to the extended mode setting
+    /// read from the configuration register.
+    pub async fn dump(&mut self) -> Result<RegisterDump, Error<E>> {
+        let temperature = self.read_register_u16(Register::Temperature).await?;
+        let config = ConfigRegister::from(self.read_register_u16(Register::Config).await?);
+        let t_low = self.read_register_u16(Register::TLow).await?;
+        let t_high = self.read_register_u16(Register::THigh).await?;
+        let extended_mode = config.is_extended_mode();
+        Ok(RegisterDump {
+            temperature: convert_temp_from_register(temperature.msb, temperature.lsb),
+            is_shutdown: config.is_shutdown(),
+            one_shot_bit: config.one_shot_bit(),
+            thermostat_mode: config.thermostat_mode(),
+            alert_polarity: config.alert_polarity(),
+            fault_queue: config.fault_queue(),
+            resolution_bits: config.resolution_bits(),
+            is_extended_mode: extended_mode,
+            alert_bit: config.alert_bit(),
+            conversion_rate: config.conversion_rate(),
+            low_temperature_threshold: convert_temp_from_register_in_mode(
+                t_low.msb,
+                t_low.lsb,
+                extended_mode,
+            ),
+            high_temperature_threshold: convert_temp_from_register_in_mode(
+                t_high.msb,
+                t_high.lsb,
+                extended_mode,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writable_registers_map_to_registers() {
+        assert_eq!(Register::Config, Register::from(WritableRegister::Config));
+        assert_eq!(Register::TLow, Register::from(WritableRegister::TLow));
+        assert_eq!(Register::THigh, Register::from(WritableRegister::THigh));
+    }
+
+    #[test]
+    fn can_decode_default_config() {
+        let config = ConfigRegister::default();
+        assert_eq!(0x60A0, config.bits());
+        assert!(!config.is_shutdown());
+        assert_eq!(ThermostatMode::Comparator, config.thermostat_mode());
+        assert_eq!(AlertPolarity::ActiveLow, config.alert_polarity());
+        assert_eq!(FaultQueue::_1, config.fault_queue());
+        assert_eq!(0b11, config.resolution_bits());
+        assert!(!config.one_shot_bit());
+        assert!(!config.is_extended_mode());
+        assert!(config.alert_bit());
+        assert_eq!(ConversionRate::_4Hz, config.conversion_rate());
+    }
+
+    #[test]
+    fn can_decode_config() {
+        let config = ConfigRegister::from_bits(0b1001_1111_0101_0000);
+        assert!(config.is_shutdown());
+        assert_eq!(ThermostatMode::Interrupt, config.thermostat_mode());
+        assert_eq!(AlertPolarity::ActiveHigh, config.alert_polarity());
+        assert_eq!(FaultQueue::_6, config.fault_queue());
+        assert_eq!(0b00, config.resolution_bits());
+        assert!(config.one_shot_bit());
+        assert!(config.is_extended_mode());
+        assert!(!config.alert_bit());
+        assert_eq!(ConversionRate::_1Hz, config.conversion_rate());
+    }
+
+    #[test]
+    fn can_decode_temperature() {
+        let normal = TemperatureRegister::from_bits(0b0110_0100_0000_0000);
+        assert!(!normal.is_extended());
+        assert_eq!(100.0, normal.celsius());
+        let extended = TemperatureRegister::from_bits(0b0100_1011_0000_0001);
+        assert!(extended.is_extended());
+        assert_eq!(150.0, extended.celsius());
+    }
+}
@@ -0,0 +1,154 @@
+//! Detection of new conversion results in continuous mode.
+
+use crate::{marker::mode, ConfigRegister, ConversionRate, Error, Tmp1x2};
+use core::time::Duration;
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Monotonic clock.
+///
+/// Returns the time elapsed since an arbitrary but fixed point in time.
+/// The value must never decrease.
+///
+/// This is implemented for closures returning a `Duration`.
+pub trait Clock {
+    /// Current time.
+    fn now(&mut self) -> Duration;
+}
+
+impl<F> Clock for F
+where
+    F: FnMut() -> Duration,
+{
+    fn now(&mut self) -> Duration {
+        self()
+    }
+}
+
+/// Tracks when a new conversion result is available in continuous mode.
+///
+/// In continuous conversion mode the device updates the temperature register
+/// once per conversion period. Reading it more often returns the same
+/// conversion again. This uses a monotonic [`Clock`](trait.Clock.html) and the
+/// conversion rate cached in the driver to only return new results.
+///
+/// The first reading is always considered new. Afterwards a new reading is
+/// available one full conversion period after the previous one.
+///
+/// The same tracker should always be used with the same device. Changing the
+/// conversion rate takes effect after the next reading.
+#[derive(Debug)]
+pub struct FreshSampleTracker<CLK> {
+    clock: CLK,
+    last_sample: Option<Duration>,
+}
+
+impl<CLK: Clock> FreshSampleTracker<CLK> {
+    /// Create a new tracker using the given clock.
+    pub fn new(clock: CLK) -> Self {
+        FreshSampleTracker {
+            clock,
+            last_sample: None,
+        }
+    }
+
+    /// Destroy the tracker, return the clock.
+    pub fn destroy(self) -> CLK {
+        self.clock
+    }
+
+    /// Forget the time of the last reading.
+    ///
+    /// The next reading will be considered new.
+    pub fn reset(&mut self) {
+        self.last_sample = None;
+    }
+
+    /// Time remaining until the next conversion result is available.
+    fn time_until_next_sample(&mut self, rate: ConversionRate) -> Duration {
+        match self.last_sample {
+            None => Duration::ZERO,
+            Some(last) => {
+                let elapsed = self.clock.now().saturating_sub(last);
+                rate.sample_period().saturating_sub(elapsed)
+            }
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "FreshSampleTracker",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<CLK: Clock> FreshSampleTracker<CLK> {
+    /// Check whether a new conversion result is available.
+    ///
+    /// This does not communicate with the device. Returns `None` if the
+    /// configuration cached in the driver is unknown, for example after a
+    /// failed rollback in
+    /// [`apply_configuration()`](struct.Tmp1x2.html#method.apply_configuration).
+    /// [`read_fresh_temperature()`](#method.read_fresh_temperature) reads it
+    /// back from the device in that case.
+    pub fn is_new_sample_available<I2C, RANGE>(
+        &mut self,
+        dev: &Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> Option<bool>
+    where
+        I2C: AsyncI2c,
+    {
+        let rate = dev.cached_conversion_rate()?;
+        Some(self.time_until_next_sample(rate) == Duration::ZERO)
+    }
+
+    /// Read the temperature if a new conversion result is available.
+    ///
+    /// Returns `nb::Error::WouldBlock` until one full conversion period has
+    /// passed since the previous reading.
+    pub async fn read_fresh_temperature<I2C, E, RANGE>(
+        &mut self,
+        dev: &mut Tmp1x2<I2C, mode::Continuous, RANGE>,
+    ) -> nb::Result<f32, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        let rate = dev.conversion_rate().await?;
+        if self.time_until_next_sample(rate) != Duration::ZERO {
+            return Err(nb::Error::WouldBlock);
+        }
+        let temperature = dev.read_temperature().await?;
+        self.last_sample = Some(self.clock.now());
+        Ok(temperature)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, MODE, RANGE> Tmp1x2<I2C, MODE, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Conversion rate in the cached configuration, if it is known.
+    pub(crate) fn cached_conversion_rate(&self) -> Option<ConversionRate> {
+        self.config_is_known
+            .then(|| ConfigRegister::from(self.config.clone()).conversion_rate())
+    }
+
+    /// Conversion rate, read from the device if the cached configuration
+    /// is unknown.
+    pub(crate) async fn conversion_rate(&mut self) -> Result<ConversionRate, Error<E>> {
+        let config = self.current_config().await?;
+        Ok(ConfigRegister::from(config).conversion_rate())
+    }
+}
@@ -0,0 +1,44 @@
+//! Consumers of temperature readings.
+
+use crate::{marker::mode, Error, Tmp1x2};
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Consumer of temperature readings.
+///
+/// This is implemented by the estimators and trackers which timestamp each
+/// reading with their own [`Clock`](trait.Clock.html). Readings can be read
+/// from the device and passed on with
+/// [`read_temperature_into()`](struct.Tmp1x2.html#method.read_temperature_into).
+pub trait TemperatureSink {
+    /// Add a temperature reading taken now.
+    fn add_temperature(&mut self, temperature: f32);
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "Tmp1x2",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, E, RANGE> Tmp1x2<I2C, mode::Continuous, RANGE>
+where
+    I2C: AsyncI2c<Error = E>,
+{
+    /// Read the temperature and add it to a sink.
+    ///
+    /// The sink is not updated if the temperature cannot be read.
+    /// Returns the temperature read.
+    pub async fn read_temperature_into<S: TemperatureSink>(
+        &mut self,
+        sink: &mut S,
+    ) -> Result<f32, Error<E>> {
+        let temperature = self.read_temperature().await?;
+        sink.add_temperature(temperature);
+        Ok(temperature)
+    }
+}
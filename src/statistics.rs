@@ -0,0 +1,257 @@
+//! Long-term statistics of the readings for telemetry reports.
+
+use crate::conversion::convert_temp_from_register_in_mode;
+use crate::{
+    stats::RunningStats, Clock, ConfigRegister, Error, RegisterU16, TemperatureSink, Tmp1x2,
+};
+use core::time::Duration;
+#[cfg(not(feature = "async"))]
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+/// Statistics of the readings since the last reset.
+///
+/// Times are clock values as returned by the [`Clock`](trait.Clock.html)
+/// of the tracker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatisticsSnapshot {
+    /// Number of readings.
+    pub count: u32,
+    /// Mean temperature in degrees Celsius.
+    pub mean: f32,
+    /// Sample variance in squared degrees Celsius.
+    ///
+    /// This is 0 for a single reading.
+    pub variance: f32,
+    /// Sample standard deviation in degrees Celsius.
+    pub standard_deviation: f32,
+    /// Lowest temperature in degrees Celsius.
+    pub min: f32,
+    /// Time of the lowest temperature.
+    pub min_time: Duration,
+    /// Highest temperature in degrees Celsius.
+    pub max: f32,
+    /// Time of the highest temperature.
+    pub max_time: Duration,
+    /// Total time the temperature was above the high threshold.
+    pub time_above_high: Duration,
+    /// Total time the temperature was below the low threshold.
+    pub time_below_low: Duration,
+    /// Time since the tracker was created or reset.
+    pub period: Duration,
+}
+
+/// Accumulates statistics of the readings.
+///
+/// Besides the count, mean, variance and the timestamped extremes, the time
+/// spent above the high threshold and below the low threshold is summed up.
+/// Each reading is assumed to hold until the next one.
+///
+/// For periodic reports use [`take_snapshot()`](#method.take_snapshot),
+/// which returns the statistics and resets the tracker. The latest reading
+/// is kept, so that the time above or below the thresholds is split
+/// correctly between the reports.
+#[derive(Debug)]
+pub struct StatisticsTracker<CLK> {
+    clock: CLK,
+    low_threshold: f32,
+    high_threshold: f32,
+    stats: RunningStats,
+    min: (f32, Duration),
+    max: (f32, Duration),
+    time_above_high: Duration,
+    time_below_low: Duration,
+    start: Duration,
+    /// Time and value of the latest reading.
+    last: Option<(Duration, f32)>,
+}
+
+impl<CLK: Clock> StatisticsTracker<CLK> {
+    /// Create a new tracker using the given clock and thresholds in
+    /// degrees Celsius.
+    ///
+    /// These thresholds are independent of the `T_LOW` and `T_HIGH`
+    /// registers of the device. To use those, create the tracker with
+    /// [`with_device_thresholds()`](#method.with_device_thresholds).
+    pub fn new(mut clock: CLK, low_threshold: f32, high_threshold: f32) -> Self {
+        let start = clock.now();
+        StatisticsTracker {
+            clock,
+            low_threshold,
+            high_threshold,
+            stats: RunningStats::new(),
+            min: (f32::INFINITY, start),
+            max: (f32::NEG_INFINITY, start),
+            time_above_high: Duration::ZERO,
+            time_below_low: Duration::ZERO,
+            start,
+            last: None,
+        }
+    }
+
+    /// Destroy the tracker, return the clock.
+    pub fn destroy(self) -> CLK {
+        self.clock
+    }
+
+    /// Statistics since the last reset.
+    ///
+    /// The time since the latest reading is included in the time above or
+    /// below the thresholds. Returns `None` if there were no readings.
+    pub fn snapshot(&mut self) -> Option<StatisticsSnapshot> {
+        let now = self.clock.now();
+        self.accumulate(now);
+        if self.stats.count() == 0 {
+            return None;
+        }
+        Some(StatisticsSnapshot {
+            count: self.stats.count(),
+            mean: self.stats.mean(),
+            variance: self.stats.variance(),
+            standard_deviation: self.stats.standard_deviation(),
+            min: self.min.0,
+            min_time: self.min.1,
+            max: self.max.0,
+            max_time: self.max.1,
+            time_above_high: self.time_above_high,
+            time_below_low: self.time_below_low,
+            period: now.saturating_sub(self.start),
+        })
+    }
+
+    /// Clear the statistics.
+    pub fn reset(&mut self) {
+        let now = self.clock.now();
+        self.accumulate(now);
+        self.stats = RunningStats::new();
+        self.min = (f32::INFINITY, now);
+        self.max = (f32::NEG_INFINITY, now);
+        self.time_above_high = Duration::ZERO;
+        self.time_below_low = Duration::ZERO;
+        self.start = now;
+    }
+
+    /// Return the statistics since the last reset and reset them.
+    pub fn take_snapshot(&mut self) -> Option<StatisticsSnapshot> {
+        let snapshot = self.snapshot();
+        self.reset();
+        snapshot
+    }
+
+    /// Add the time since the latest reading to the time above or below
+    /// the thresholds.
+    fn accumulate(&mut self, now: Duration) {
+        if let Some((time, temperature)) = self.last {
+            let elapsed = now.saturating_sub(time);
+            if temperature > self.high_threshold {
+                self.time_above_high += elapsed;
+            } else if temperature < self.low_threshold {
+                self.time_below_low += elapsed;
+            }
+            self.last = Some((now, temperature));
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(
+        cfg(not(feature = "async")),
+        self = "StatisticsTracker",
+        idents(AsyncI2c(sync = "I2c"))
+    ),
+    async(feature = "async", keep_self)
+)]
+impl<CLK: Clock> StatisticsTracker<CLK> {
+    /// Create a new tracker using the given clock and the low and high
+    /// temperature thresholds currently set in the device.
+    ///
+    /// The thresholds are read once. Later changes of the device thresholds
+    /// are not followed.
+    pub async fn with_device_thresholds<I2C, E, MODE, RANGE>(
+        clock: CLK,
+        dev: &mut Tmp1x2<I2C, MODE, RANGE>,
+    ) -> Result<Self, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+    {
+        let registers = dev.read_configuration_registers().await?;
+        let extended_mode = ConfigRegister::from(registers.config).is_extended_mode();
+        let decode = |data: RegisterU16| {
+            convert_temp_from_register_in_mode(data.msb, data.lsb, extended_mode)
+        };
+        Ok(Self::new(
+            clock,
+            decode(registers.t_low),
+            decode(registers.t_high),
+        ))
+    }
+}
+
+impl<CLK: Clock> TemperatureSink for StatisticsTracker<CLK> {
+    fn add_temperature(&mut self, temperature: f32) {
+        let now = self.clock.now();
+        self.accumulate(now);
+        self.stats.add(temperature);
+        if temperature < self.min.0 {
+            self.min = (temperature, now);
+        }
+        if temperature > self.max.0 {
+            self.max = (temperature, now);
+        }
+        self.last = Some((now, temperature));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    fn secs(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn no_snapshot_without_readings() {
+        let mut tracker = StatisticsTracker::new(|| Duration::ZERO, 10.0, 30.0);
+        assert_eq!(None, tracker.snapshot());
+    }
+
+    #[test]
+    fn can_track_statistics() {
+        let now = Cell::new(Duration::ZERO);
+        let mut tracker = StatisticsTracker::new(|| now.get(), 10.0, 30.0);
+        for (seconds, temperature) in [(0, 20.0), (10, 35.0), (25, 5.0), (30, 20.0)] {
+            now.set(secs(seconds));
+            tracker.add_temperature(temperature);
+        }
+        now.set(secs(40));
+        let snapshot = tracker.snapshot().unwrap();
+        assert_eq!(4, snapshot.count);
+        assert!(f32::abs(snapshot.mean - 20.0) < 1e-5);
+        assert!(f32::abs(snapshot.variance - 150.0) < 1e-3);
+        assert_eq!((5.0, secs(25)), (snapshot.min, snapshot.min_time));
+        assert_eq!((35.0, secs(10)), (snapshot.max, snapshot.max_time));
+        assert_eq!(secs(15), snapshot.time_above_high);
+        assert_eq!(secs(5), snapshot.time_below_low);
+        assert_eq!(secs(40), snapshot.period);
+    }
+
+    #[test]
+    fn time_is_split_between_snapshots() {
+        let now = Cell::new(Duration::ZERO);
+        let mut tracker = StatisticsTracker::new(|| now.get(), 10.0, 30.0);
+        tracker.add_temperature(40.0);
+        now.set(secs(10));
+        let first = tracker.take_snapshot().unwrap();
+        assert_eq!(secs(10), first.time_above_high);
+        now.set(secs(15));
+        tracker.add_temperature(20.0);
+        let second = tracker.take_snapshot().unwrap();
+        assert_eq!(1, second.count);
+        assert_eq!(secs(5), second.time_above_high);
+        assert_eq!(secs(5), second.period);
+    }
+}
@@ -0,0 +1,88 @@
+//! Running statistics shared by the measurement helpers.
+
+/// Running mean and variance using Welford's algorithm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RunningStats {
+    count: u32,
+    mean: f32,
+    m2: f32,
+    min: f32,
+    max: f32,
+}
+
+impl RunningStats {
+    pub(crate) const fn new() -> Self {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f32::INFINITY,
+            max: f32::NEG_INFINITY,
+        }
+    }
+
+    pub(crate) fn add(&mut self, value: f32) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    pub(crate) fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub(crate) fn mean(&self) -> f32 {
+        self.mean
+    }
+
+    /// Sample variance. This is 0 for less than two values.
+    pub(crate) fn variance(&self) -> f32 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f32
+        }
+    }
+
+    /// Sample standard deviation. This is 0 for less than two values.
+    pub(crate) fn standard_deviation(&self) -> f32 {
+        libm::sqrtf(self.variance())
+    }
+
+    pub(crate) fn min(&self) -> f32 {
+        self.min
+    }
+
+    pub(crate) fn max(&self) -> f32 {
+        self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RunningStats;
+
+    #[test]
+    fn can_calculate_statistics() {
+        let mut stats = RunningStats::new();
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.add(value);
+        }
+        assert_eq!(8, stats.count());
+        assert!(f32::abs(stats.mean() - 5.0) < 1e-6);
+        assert!(f32::abs(stats.variance() - 4.571_428) < 1e-5);
+        assert!(f32::abs(stats.standard_deviation() - 2.138_09) < 1e-5);
+        assert!(f32::abs(stats.min() - 2.0) < f32::EPSILON);
+        assert!(f32::abs(stats.max() - 9.0) < f32::EPSILON);
+    }
+
+    #[test]
+    fn standard_deviation_of_single_value_is_zero() {
+        let mut stats = RunningStats::new();
+        stats.add(25.0);
+        assert!(f32::abs(stats.standard_deviation()) < f32::EPSILON);
+    }
+}
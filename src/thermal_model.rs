@@ -0,0 +1,217 @@
+//! Online fit of a first-order thermal model.
+
+use crate::{Clock, TemperatureSink};
+use core::time::Duration;
+
+/// Predicted steady state of a first-order thermal model.
+///
+/// See [`ThermalModel`](struct.ThermalModel.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SteadyStatePrediction {
+    /// Temperature the readings are approaching in degrees Celsius.
+    pub temperature: f32,
+    /// Time constant of the model.
+    ///
+    /// After one time constant about 63% of the remaining change is done,
+    /// after five about 99%.
+    pub time_constant: Duration,
+    /// Coefficient of determination of the fit between 0 and 1.
+    ///
+    /// Values close to 1 mean that the readings follow the model well.
+    pub confidence: f32,
+}
+
+/// Minimum number of rate of change samples needed for a prediction.
+const MIN_SAMPLES: u32 = 3;
+
+/// Fits a first-order lag model to timestamped readings online.
+///
+/// In a first-order model the temperature approaches a final value
+/// exponentially with a time constant `tau`, so that its rate of change is
+/// proportional to the remaining difference:
+/// `dT/dt = (T_final - T) / tau`.
+///
+/// The rate of change between consecutive readings is regressed against
+/// their mean temperature with exponential forgetting: each new sample
+/// multiplies the weight of the previous ones by the forgetting factor,
+/// so that the model follows slow changes of the conditions. The effective
+/// memory is about `1 / (1 - forgetting_factor)` samples.
+///
+/// The readings are added through [`TemperatureSink`](trait.TemperatureSink.html)
+/// and timestamped with a monotonic [`Clock`](trait.Clock.html).
+/// They should be far enough apart for the temperature to change by several
+/// resolution steps, otherwise the quantization noise dominates the rate of
+/// change.
+#[derive(Debug)]
+pub struct ThermalModel<CLK> {
+    clock: CLK,
+    forgetting_factor: f64,
+    last: Option<(Duration, f32)>,
+    /// Temperatures are stored relative to the first reading.
+    reference: f32,
+    samples: u32,
+    sum_w: f64,
+    sum_x: f64,
+    sum_y: f64,
+    sum_xx: f64,
+    sum_xy: f64,
+    sum_yy: f64,
+}
+
+impl<CLK: Clock> ThermalModel<CLK> {
+    /// Create a new model using the given clock.
+    ///
+    /// The forgetting factor is clamped to the range 0 to 1. A value of 1
+    /// weights all samples equally.
+    pub fn new(clock: CLK, forgetting_factor: f32) -> Self {
+        ThermalModel {
+            clock,
+            forgetting_factor: f64::from(forgetting_factor.clamp(0.0, 1.0)),
+            last: None,
+            reference: 0.0,
+            samples: 0,
+            sum_w: 0.0,
+            sum_x: 0.0,
+            sum_y: 0.0,
+            sum_xx: 0.0,
+            sum_xy: 0.0,
+            sum_yy: 0.0,
+        }
+    }
+
+    /// Destroy the model, return the clock.
+    pub fn destroy(self) -> CLK {
+        self.clock
+    }
+
+    /// Forget all readings.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.samples = 0;
+        self.sum_w = 0.0;
+        self.sum_x = 0.0;
+        self.sum_y = 0.0;
+        self.sum_xx = 0.0;
+        self.sum_xy = 0.0;
+        self.sum_yy = 0.0;
+    }
+
+    /// Predicted steady state.
+    ///
+    /// Returns `None` until enough readings were added or if the readings
+    /// do not approach a final value.
+    pub fn prediction(&self) -> Option<SteadyStatePrediction> {
+        if self.samples < MIN_SAMPLES {
+            return None;
+        }
+        let w = self.sum_w;
+        let (mean_x, mean_y) = (self.sum_x / w, self.sum_y / w);
+        let var_x = self.sum_xx / w - mean_x * mean_x;
+        let var_y = self.sum_yy / w - mean_y * mean_y;
+        let cov = self.sum_xy / w - mean_x * mean_y;
+        if var_x <= 0.0 {
+            return None;
+        }
+        let slope = cov / var_x;
+        if slope >= 0.0 {
+            return None;
+        }
+        let intercept = mean_y - slope * mean_x;
+        let confidence = if var_y > 0.0 {
+            cov * cov / (var_x * var_y)
+        } else {
+            1.0
+        };
+        Some(SteadyStatePrediction {
+            temperature: self.reference + (-intercept / slope) as f32,
+            time_constant: Duration::try_from_secs_f64(-1.0 / slope).ok()?,
+            confidence: confidence.clamp(0.0, 1.0) as f32,
+        })
+    }
+}
+
+impl<CLK: Clock> TemperatureSink for ThermalModel<CLK> {
+    /// Readings taken at the same time as the previous one are ignored.
+    fn add_temperature(&mut self, temperature: f32) {
+        let now = self.clock.now();
+        let (time, last) = match self.last {
+            None => {
+                self.reference = temperature;
+                self.last = Some((now, temperature));
+                return;
+            }
+            Some(last) => last,
+        };
+        let dt = now.saturating_sub(time).as_secs_f64();
+        if dt == 0.0 {
+            return;
+        }
+        let x = f64::from((temperature + last) / 2.0 - self.reference);
+        let y = f64::from(temperature - last) / dt;
+        let lambda = self.forgetting_factor;
+        self.sum_w = lambda * self.sum_w + 1.0;
+        self.sum_x = lambda * self.sum_x + x;
+        self.sum_y = lambda * self.sum_y + y;
+        self.sum_xx = lambda * self.sum_xx + x * x;
+        self.sum_xy = lambda * self.sum_xy + x * y;
+        self.sum_yy = lambda * self.sum_yy + y * y;
+        self.samples = self.samples.saturating_add(1);
+        self.last = Some((now, temperature));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Feed readings of `40 - 20 * exp(-t / 100)` every 10 seconds.
+    fn feed(model: &mut ThermalModel<impl Clock>, now: &Cell<Duration>, count: u64) {
+        for i in 0..count {
+            let t = (i * 10) as f64;
+            now.set(Duration::from_secs(i * 10));
+            model.add_temperature((40.0 - 20.0 * libm::exp(-t / 100.0)) as f32);
+        }
+    }
+
+    #[test]
+    fn no_prediction_without_enough_readings() {
+        let now = Cell::new(Duration::ZERO);
+        let mut model = ThermalModel::new(|| now.get(), 1.0);
+        feed(&mut model, &now, 3);
+        assert_eq!(None, model.prediction());
+    }
+
+    #[test]
+    fn can_predict_steady_state() {
+        let now = Cell::new(Duration::ZERO);
+        let mut model = ThermalModel::new(|| now.get(), 0.95);
+        feed(&mut model, &now, 10);
+        let prediction = model.prediction().unwrap();
+        assert!(f32::abs(prediction.temperature - 40.0) < 0.01);
+        let tau = prediction.time_constant.as_secs_f32();
+        assert!(f32::abs(tau - 100.0) < 0.5);
+        assert!(prediction.confidence > 0.999);
+    }
+
+    #[test]
+    fn no_prediction_for_diverging_readings() {
+        let now = Cell::new(Duration::ZERO);
+        let mut model = ThermalModel::new(|| now.get(), 1.0);
+        for (i, temperature) in [20.0, 21.0, 23.0, 27.0, 35.0].iter().enumerate() {
+            now.set(Duration::from_secs(i as u64));
+            model.add_temperature(*temperature);
+        }
+        assert_eq!(None, model.prediction());
+    }
+
+    #[test]
+    fn reset_forgets_readings() {
+        let now = Cell::new(Duration::ZERO);
+        let mut model = ThermalModel::new(|| now.get(), 1.0);
+        feed(&mut model, &now, 10);
+        model.reset();
+        assert_eq!(None, model.prediction());
+    }
+}
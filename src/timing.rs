@@ -0,0 +1,113 @@
+//! Timing characteristics of the conversion rates and the fault queue.
+//!
+//! Conversion times are the datasheet values, which are the same for the
+//! TMP102 and the TMP112 family.
+
+use crate::{ConversionRate, FaultQueue};
+use core::time::Duration;
+
+impl ConversionRate {
+    /// All conversion rates, from the slowest to the fastest.
+    const ALL: [ConversionRate; 4] = [
+        ConversionRate::_0_25Hz,
+        ConversionRate::_1Hz,
+        ConversionRate::_4Hz,
+        ConversionRate::_8Hz,
+    ];
+
+    /// Time between the start of two consecutive conversions in continuous
+    /// conversion mode.
+    pub const fn sample_period(self) -> Duration {
+        match self {
+            ConversionRate::_0_25Hz => Duration::from_secs(4),
+            ConversionRate::_1Hz => Duration::from_secs(1),
+            ConversionRate::_4Hz => Duration::from_millis(250),
+            ConversionRate::_8Hz => Duration::from_millis(125),
+        }
+    }
+
+    /// Typical duration of a single conversion (26 ms).
+    ///
+    /// This does not depend on the conversion rate.
+    pub const fn typical_conversion_time(self) -> Duration {
+        Duration::from_millis(26)
+    }
+
+    /// Maximum duration of a single conversion (35 ms).
+    ///
+    /// This does not depend on the conversion rate.
+    pub const fn max_conversion_time(self) -> Duration {
+        Duration::from_millis(35)
+    }
+
+    /// Get the slowest conversion rate whose worst-case alert latency with
+    /// the given fault queue does not exceed `max_latency`.
+    ///
+    /// Returns `None` if not even the fastest conversion rate meets it.
+    /// See [`FaultQueue::worst_case_alert_latency()`](enum.FaultQueue.html#method.worst_case_alert_latency).
+    pub fn slowest_meeting_latency(
+        fault_queue: FaultQueue,
+        max_latency: Duration,
+    ) -> Option<ConversionRate> {
+        Self::ALL
+            .into_iter()
+            .find(|rate| fault_queue.worst_case_alert_latency(*rate) <= max_latency)
+    }
+}
+
+impl FaultQueue {
+    /// Number of consecutive faults necessary to trigger an alert.
+    pub const fn consecutive_faults(self) -> u8 {
+        match self {
+            FaultQueue::_1 => 1,
+            FaultQueue::_2 => 2,
+            FaultQueue::_4 => 4,
+            FaultQueue::_6 => 6,
+        }
+    }
+
+    /// Worst-case time between the temperature crossing a threshold and
+    /// the alert being updated in continuous conversion mode.
+    ///
+    /// If the threshold is crossed just after a conversion started, that
+    /// conversion may not see it. Each of the following conversions needed
+    /// to fill the fault queue takes one sample period and the last one
+    /// takes up to the maximum conversion time to complete:
+    ///
+    /// `latency = consecutive faults × sample period + maximum conversion time`
+    pub fn worst_case_alert_latency(self, rate: ConversionRate) -> Duration {
+        rate.sample_period() * u32::from(self.consecutive_faults()) + rate.max_conversion_time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_calculate_alert_latency() {
+        assert_eq!(
+            Duration::from_millis(160),
+            FaultQueue::_1.worst_case_alert_latency(ConversionRate::_8Hz)
+        );
+        assert_eq!(
+            Duration::from_millis(1035),
+            FaultQueue::_4.worst_case_alert_latency(ConversionRate::_4Hz)
+        );
+        assert_eq!(
+            Duration::from_millis(24035),
+            FaultQueue::_6.worst_case_alert_latency(ConversionRate::_0_25Hz)
+        );
+    }
+
+    #[test]
+    fn can_select_slowest_rate_meeting_latency() {
+        let select =
+            |fq, ms| ConversionRate::slowest_meeting_latency(fq, Duration::from_millis(ms));
+        assert_eq!(Some(ConversionRate::_0_25Hz), select(FaultQueue::_1, 4035));
+        assert_eq!(Some(ConversionRate::_1Hz), select(FaultQueue::_1, 4034));
+        assert_eq!(Some(ConversionRate::_4Hz), select(FaultQueue::_2, 535));
+        assert_eq!(Some(ConversionRate::_8Hz), select(FaultQueue::_6, 785));
+        assert_eq!(None, select(FaultQueue::_6, 784));
+    }
+}
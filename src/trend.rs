@@ -0,0 +1,207 @@
+//! Rate of change estimation over a window of timestamped readings.
+
+use crate::{Clock, TemperatureSink};
+use core::time::Duration;
+
+/// Settings for [`TrendEstimator`](struct.TrendEstimator.html).
+///
+/// Rates of change are in degrees Celsius per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrendSettings {
+    /// The temperature is rising fast when the slope is at or above this
+    /// value.
+    pub rising_rate: f32,
+    /// The temperature is falling fast when the slope is at or below the
+    /// negative of this value.
+    pub falling_rate: f32,
+}
+
+/// Temperature trend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Trend {
+    /// The temperature changes slower than the thresholds.
+    Stable,
+    /// The temperature is rising at least at the rising rate.
+    RisingFast,
+    /// The temperature is falling at least at the falling rate.
+    FallingFast,
+}
+
+/// Estimates the rate of change of the temperature.
+///
+/// The last `N` readings are timestamped with a monotonic
+/// [`Clock`](trait.Clock.html) and a straight line is fitted through them
+/// by least squares. Its slope is the rate of change in degrees Celsius per
+/// second. `N` must be at least 2, which is checked at compile time:
+///
+/// ```compile_fail
+/// use core::time::Duration;
+/// use tmp1x2::{TrendEstimator, TrendSettings};
+///
+/// let settings = TrendSettings {
+///     rising_rate: 0.5,
+///     falling_rate: 0.5,
+/// };
+/// let trend = TrendEstimator::<_, 1>::new(|| Duration::ZERO, settings);
+/// ```
+#[derive(Debug)]
+pub struct TrendEstimator<CLK, const N: usize> {
+    clock: CLK,
+    settings: TrendSettings,
+    readings: [(Duration, f32); N],
+    next: usize,
+    len: usize,
+}
+
+impl<CLK: Clock, const N: usize> TrendEstimator<CLK, N> {
+    const ENOUGH_READINGS: () = assert!(N >= 2, "the window size must be at least 2");
+
+    /// Create a new estimator using the given clock.
+    pub fn new(clock: CLK, settings: TrendSettings) -> Self {
+        // Evaluated at compile time for each window size used
+        let () = Self::ENOUGH_READINGS;
+        TrendEstimator {
+            clock,
+            settings,
+            readings: [(Duration::ZERO, 0.0); N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Destroy the estimator, return the clock.
+    pub fn destroy(self) -> CLK {
+        self.clock
+    }
+
+    /// Forget all readings.
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Rate of change in degrees Celsius per second.
+    ///
+    /// Returns `None` until two readings at different times were added.
+    pub fn slope(&self) -> Option<f32> {
+        self.fit().map(|(slope, _)| slope)
+    }
+
+    /// Trend of the temperature compared against the configured rates.
+    ///
+    /// Returns `None` if the slope is not known yet.
+    pub fn trend(&self) -> Option<Trend> {
+        let slope = self.slope()?;
+        Some(if slope >= self.settings.rising_rate {
+            Trend::RisingFast
+        } else if slope <= -self.settings.falling_rate {
+            Trend::FallingFast
+        } else {
+            Trend::Stable
+        })
+    }
+
+    /// Predicted time from the latest reading until the temperature reaches
+    /// `threshold` if the current trend continues.
+    ///
+    /// This is zero if the fitted temperature is already at or beyond the
+    /// threshold in the direction of the trend. Returns `None` if the slope
+    /// is not known yet or is too small for the time to be representable.
+    pub fn time_to_threshold(&self, threshold: f32) -> Option<Duration> {
+        let (slope, latest) = self.fit()?;
+        if slope == 0.0 {
+            return None;
+        }
+        let seconds = (threshold - latest) / slope;
+        Duration::try_from_secs_f32(seconds.max(0.0)).ok()
+    }
+
+    /// Slope and fitted temperature at the time of the latest reading.
+    fn fit(&self) -> Option<(f32, f32)> {
+        let readings = &self.readings[..self.len];
+        let newest = readings.iter().map(|(time, _)| *time).max()?;
+        // Times relative to the latest reading keep the values small.
+        let x = |time: Duration| -(newest - time).as_secs_f32();
+        let n = readings.len() as f32;
+        let mean_x = readings.iter().map(|(t, _)| x(*t)).sum::<f32>() / n;
+        let mean_y = readings.iter().map(|(_, y)| y).sum::<f32>() / n;
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for (t, y) in readings {
+            let dx = x(*t) - mean_x;
+            sxy += dx * (y - mean_y);
+            sxx += dx * dx;
+        }
+        if sxx == 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        Some((slope, mean_y - slope * mean_x))
+    }
+}
+
+impl<CLK: Clock, const N: usize> TemperatureSink for TrendEstimator<CLK, N> {
+    fn add_temperature(&mut self, temperature: f32) {
+        self.readings[self.next] = (self.clock.now(), temperature);
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    const SETTINGS: TrendSettings = TrendSettings {
+        rising_rate: 0.5,
+        falling_rate: 0.2,
+    };
+
+    fn estimator<'a>(
+        now: &'a Cell<Duration>,
+        readings: &[(u64, f32)],
+    ) -> TrendEstimator<impl FnMut() -> Duration + 'a, 4> {
+        let mut trend = TrendEstimator::new(|| now.get(), SETTINGS);
+        for (seconds, temperature) in readings {
+            now.set(Duration::from_secs(*seconds));
+            trend.add_temperature(*temperature);
+        }
+        trend
+    }
+
+    #[test]
+    fn slope_is_unknown_until_two_readings() {
+        let now = Cell::new(Duration::ZERO);
+        let trend = estimator(&now, &[(0, 20.0)]);
+        assert_eq!(None, trend.slope());
+        assert_eq!(None, trend.trend());
+        assert_eq!(None, trend.time_to_threshold(30.0));
+    }
+
+    #[test]
+    fn can_fit_slope() {
+        let now = Cell::new(Duration::ZERO);
+        let trend = estimator(&now, &[(0, 20.0), (1, 21.0), (2, 21.5), (3, 23.0)]);
+        assert!(f32::abs(trend.slope().unwrap() - 0.95) < 1e-5);
+        assert_eq!(Some(Trend::RisingFast), trend.trend());
+    }
+
+    #[test]
+    fn uses_last_readings() {
+        let now = Cell::new(Duration::ZERO);
+        let readings = [(0, 50.0), (10, 20.0), (20, 19.0), (30, 18.0), (40, 17.0)];
+        let trend = estimator(&now, &readings);
+        assert!(f32::abs(trend.slope().unwrap() + 0.1) < 1e-5);
+        assert_eq!(Some(Trend::Stable), trend.trend());
+    }
+
+    #[test]
+    fn can_predict_time_to_threshold() {
+        let now = Cell::new(Duration::ZERO);
+        let trend = estimator(&now, &[(0, 30.0), (1, 29.0), (2, 28.0)]);
+        assert_eq!(Some(Trend::FallingFast), trend.trend());
+        assert_eq!(Some(Duration::from_secs(8)), trend.time_to_threshold(20.0));
+        assert_eq!(Some(Duration::ZERO), trend.time_to_threshold(29.0));
+    }
+}
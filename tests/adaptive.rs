@@ -0,0 +1,158 @@
+use core::cell::Cell;
+use core::time::Duration;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{
+    AdaptiveSampler, AdaptiveSamplingSettings, ConversionMode, ConversionRate, DynTmp1x2,
+};
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, BitFlagsLow as BFL, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+const SETTINGS: AdaptiveSamplingSettings = AdaptiveSamplingSettings {
+    slow_interval: Duration::from_secs(60),
+    fast_rate: ConversionRate::_8Hz,
+    fast_threshold: 0.05,
+    settle_threshold: 0.02,
+    settle_time: Duration::from_millis(500),
+};
+
+const FAST_LSB: u8 = DEFAULT_LSB | BFL::CONV_RATE1 | BFL::CONV_RATE0;
+
+fn one_shot_expectations(temperature_msb: u8) -> [I2cTransaction; 3] {
+    [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::read(
+            DEVICE_ADDRESS,
+            vec![DEFAULT_MSB | BFH::ONE_SHOT, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![temperature_msb, 0],
+        ),
+    ]
+}
+
+fn millis(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn changes_mode_with_rate_of_change() {
+    let mut expectations = vec![I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+    )];
+    expectations.extend(one_shot_expectations(25));
+    expectations.extend(one_shot_expectations(30));
+    expectations.extend([
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, FAST_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, FAST_LSB],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![30, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![30, 0]),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, FAST_LSB],
+        ),
+    ]);
+    let dev = setup(&expectations).into_one_shot().await.unwrap();
+    let now = Cell::new(Duration::ZERO);
+    let mut sampler = AdaptiveSampler::new(DynTmp1x2::from(dev), || now.get(), SETTINGS);
+
+    assert!(sampler.read_temperature().await.is_err());
+    now.set(millis(30));
+    let temp = sampler.read_temperature().await.unwrap();
+    assert_eq!(25, temp as i16);
+    now.set(millis(1_000));
+    match sampler.read_temperature().await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+
+    now.set(millis(60_030));
+    assert!(sampler.read_temperature().await.is_err());
+    now.set(millis(60_060));
+    let temp = sampler.read_temperature().await.unwrap();
+    assert_eq!(30, temp as i16);
+    assert_eq!(ConversionMode::Continuous, sampler.mode());
+
+    now.set(millis(60_100));
+    match sampler.read_temperature().await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+    now.set(millis(60_200));
+    sampler.read_temperature().await.unwrap();
+    assert_eq!(ConversionMode::Continuous, sampler.mode());
+    now.set(millis(60_800));
+    sampler.read_temperature().await.unwrap();
+    assert_eq!(ConversionMode::OneShot, sampler.mode());
+
+    let (dev, _) = sampler.destroy();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn retries_failed_mode_change() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![30, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![30, 0]),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        )
+        .with_error(ErrorKind::Other),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+    ];
+    let now = Cell::new(Duration::ZERO);
+    let settings = AdaptiveSamplingSettings {
+        fast_rate: ConversionRate::_4Hz,
+        settle_time: Duration::ZERO,
+        ..SETTINGS
+    };
+    let mut sampler = AdaptiveSampler::new(
+        DynTmp1x2::from(setup(&expectations)),
+        || now.get(),
+        settings,
+    );
+
+    sampler.read_temperature().await.unwrap();
+    now.set(millis(250));
+    let temp = sampler.read_temperature().await.unwrap();
+    assert_eq!(30, temp as i16);
+    assert_eq!(ConversionMode::Continuous, sampler.mode());
+    match sampler.read_temperature().await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+    assert_eq!(ConversionMode::OneShot, sampler.mode());
+
+    let (dev, _) = sampler.destroy();
+    dev.destroy().done();
+}
@@ -0,0 +1,38 @@
+use core::time::Duration;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{AgingAccumulator, AgingSettings, AgingState};
+
+mod common;
+use common::{read_at, setup, Register, TestClock, DEVICE_ADDRESS};
+
+const SETTINGS: AgingSettings = AgingSettings {
+    activation_energy: 0.7,
+    reference_temperature: 55.0,
+    rated_life: Duration::from_secs(1000 * 3600),
+};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_continue_from_stored_state() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![55, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![55, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let stored = AgingState {
+        equivalent_seconds: 400.0 * 3600.0,
+        operating_seconds: 300.0 * 3600.0,
+    }
+    .to_bytes();
+    let state = AgingState::from_bytes(&stored).unwrap();
+    let clock = TestClock::default();
+    let mut aging = AgingAccumulator::with_state(|| clock.now(), SETTINGS, state);
+    read_at(&mut dev, &mut aging, &clock, 0).await;
+    read_at(&mut dev, &mut aging, &clock, 100 * 3600).await;
+    assert!(f64::abs(aging.equivalent_hours() - 500.0) < 1e-9);
+    assert!(f32::abs(aging.remaining_life_percent() - 50.0) < 1e-4);
+    assert!(f64::abs(aging.state().operating_seconds - 400.0 * 3600.0) < 1e-6);
+    dev.destroy().done();
+}
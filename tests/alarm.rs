@@ -0,0 +1,66 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{AlarmEvent, AlarmLevel, AlarmManager, AlarmSettings, AlarmState, Error, FaultQueue};
+
+mod common;
+use common::{setup, Register, DEVICE_ADDRESS};
+
+const SETTINGS: AlarmSettings = AlarmSettings {
+    threshold: 40.0,
+    hysteresis: 2.0,
+    debounce: FaultQueue::_2,
+};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_raise_alarm_from_readings() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![40, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![41, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let mut alarms = AlarmManager::<4>::new(SETTINGS, SETTINGS);
+    let temperature = alarms.update(&mut dev).await.unwrap();
+    assert_eq!(40.0, temperature);
+    assert_eq!(None, alarms.pop_event());
+    alarms.update(&mut dev).await.unwrap();
+    assert_eq!(
+        AlarmState::Unacknowledged,
+        alarms.state(AlarmLevel::Warning)
+    );
+    assert_eq!(
+        Some(AlarmEvent::Raised {
+            level: AlarmLevel::Warning,
+            temperature: 41.0
+        }),
+        alarms.pop_event()
+    );
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn read_error_does_not_change_alarms() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![40, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![41, 0]).with_error(ErrorKind::Other),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![30, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![41, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let mut alarms = AlarmManager::<4>::new(SETTINGS, SETTINGS);
+    alarms.update(&mut dev).await.unwrap();
+    match alarms.update(&mut dev).await {
+        Err(Error::I2C(_)) => (),
+        _ => panic!(),
+    }
+    alarms.update(&mut dev).await.unwrap();
+    alarms.update(&mut dev).await.unwrap();
+    assert_eq!(AlarmState::Normal, alarms.state(AlarmLevel::Warning));
+    assert_eq!(None, alarms.pop_event());
+    dev.destroy().done();
+}
@@ -0,0 +1,112 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use tmp1x2::{BorrowingTmp1x2, ModeChangeError, SlaveAddr};
+
+mod common;
+use common::{
+    BitFlagsHigh as BFH, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+macro_rules! assert_near {
+    ($left:expr, $right:expr) => {
+        assert!(f32::abs($left - $right) < f32::EPSILON);
+    };
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_share_bus_between_drivers() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS | 1,
+            vec![Register::TEMPERATURE],
+            vec![0b0011_0010, 0],
+        ),
+        // The pointer register is still known for both devices.
+        I2cTransaction::read(DEVICE_ADDRESS, vec![0b0110_0100, 0]),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let mut sensor1 = BorrowingTmp1x2::new(SlaveAddr::default());
+    let mut sensor2 = BorrowingTmp1x2::new(SlaveAddr::Alternative(false, true));
+    let temp = sensor1.bind(&mut i2c).read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    let temp = sensor2.bind(&mut i2c).read_temperature().await.unwrap();
+    assert_near!(50.0, temp);
+    let temp = sensor1.bind(&mut i2c).read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    i2c.done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn keeps_one_shot_state_between_calls() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::read(
+            DEVICE_ADDRESS,
+            vec![DEFAULT_MSB | BFH::ONE_SHOT, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let sensor = BorrowingTmp1x2::new(SlaveAddr::default());
+    let mut sensor = sensor.into_one_shot(&mut i2c).await.unwrap();
+    match sensor.bind(&mut i2c).read_temperature().await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+    let temp = sensor.bind(&mut i2c).read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    i2c.done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn mode_change_error_returns_unchanged_driver() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        )
+        .with_error(ErrorKind::Other),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+    ];
+    let mut i2c = I2cMock::new(&expectations);
+    let sensor = BorrowingTmp1x2::new(SlaveAddr::default());
+    let sensor = match sensor.into_one_shot(&mut i2c).await {
+        Err(ModeChangeError::I2C(ErrorKind::Other, sensor)) => sensor,
+        _ => panic!("expected a mode change error"),
+    };
+    sensor.into_one_shot(&mut i2c).await.unwrap();
+    i2c.done();
+}
@@ -0,0 +1,108 @@
+use core::num::NonZeroU8;
+use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::BurstError;
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+macro_rules! assert_near {
+    ($left:expr, $right:expr) => {
+        assert!(f32::abs($left - $right) < 1e-5);
+    };
+}
+
+fn trigger() -> I2cTransaction {
+    I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![
+            Register::CONFIG,
+            DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+            DEFAULT_LSB,
+        ],
+    )
+}
+
+fn config_read(ready: bool) -> I2cTransaction {
+    let one_shot = if ready { BFH::ONE_SHOT } else { 0 };
+    I2cTransaction::read(
+        DEVICE_ADDRESS,
+        vec![DEFAULT_MSB | one_shot | BFH::SHUTDOWN, DEFAULT_LSB],
+    )
+}
+
+fn temperature_read(msb: u8, lsb: u8) -> I2cTransaction {
+    I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![msb, lsb])
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_read_temperature_burst() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        trigger(),
+        config_read(true),
+        temperature_read(25, 0),
+        trigger(),
+        config_read(false),
+        config_read(true),
+        temperature_read(25, 0b1000_0000),
+    ];
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_ms(35),
+        DelayTransaction::delay_ms(35),
+        DelayTransaction::delay_ms(1),
+    ]);
+    let mut dev = setup(&expectations).into_one_shot().await.unwrap();
+    let stats = dev
+        .read_temperature_burst(&mut delay, NonZeroU8::new(2).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(2, stats.count);
+    assert_near!(25.25, stats.mean);
+    assert_near!(0.353_553, stats.standard_deviation);
+    assert_near!(25.0, stats.min);
+    assert_near!(25.5, stats.max);
+    dev.destroy().done();
+    delay.done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn burst_times_out_if_measurement_never_finishes() {
+    let mut expectations = vec![
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        trigger(),
+    ];
+    let mut delays = vec![DelayTransaction::delay_ms(35)];
+    // Waiting for three maximum conversion times in total
+    for _ in 0..70 {
+        expectations.push(config_read(false));
+        delays.push(DelayTransaction::delay_ms(1));
+    }
+    expectations.push(config_read(false));
+    let mut delay = CheckedDelay::new(&delays);
+    let mut dev = setup(&expectations).into_one_shot().await.unwrap();
+    match dev
+        .read_temperature_burst(&mut delay, NonZeroU8::new(1).unwrap())
+        .await
+    {
+        Err(BurstError::Timeout) => (),
+        _ => panic!(),
+    }
+    dev.destroy().done();
+    delay.done();
+}
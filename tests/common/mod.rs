@@ -1,5 +1,7 @@
+use core::cell::Cell;
+use core::time::Duration;
 use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
-use tmp1x2::{marker, SlaveAddr, Tmp1x2};
+use tmp1x2::{marker, SlaveAddr, TemperatureSink, Tmp1x2};
 
 pub const DEVICE_ADDRESS: u8 = 0b100_1000;
 
@@ -36,10 +38,45 @@ impl BitFlagsLow {
     pub const CONV_RATE1: u8 = 0b1000_0000;
 }
 
+#[allow(unused)]
 pub const DEFAULT_CONFIG_MSB: u8 = BitFlagsHigh::RESOLUTION;
+#[allow(unused)]
 pub const DEFAULT_CONFIG_LSB: u8 = BitFlagsLow::CONV_RATE1 | BitFlagsLow::ALERT;
 
+#[allow(unused)]
 pub fn setup(expectations: &[I2cTransaction]) -> Tmp1x2<I2cMock, marker::mode::Continuous> {
     let i2c = I2cMock::new(expectations);
     Tmp1x2::new(i2c, SlaveAddr::default())
 }
+
+/// Clock set by the test.
+#[allow(unused)]
+#[derive(Default)]
+pub struct TestClock(Cell<Duration>);
+
+#[allow(unused)]
+impl TestClock {
+    pub fn now(&self) -> Duration {
+        self.0.get()
+    }
+
+    pub fn set(&self, seconds: u64) {
+        self.0.set(Duration::from_secs(seconds));
+    }
+}
+
+/// Set the clock and read the temperature into the sink.
+#[allow(unused)]
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), keep_self),
+    async(feature = "async", keep_self)
+)]
+pub async fn read_at<S: TemperatureSink>(
+    dev: &mut Tmp1x2<I2cMock, marker::mode::Continuous>,
+    sink: &mut S,
+    clock: &TestClock,
+    seconds: u64,
+) -> f32 {
+    clock.set(seconds);
+    dev.read_temperature_into(sink).await.unwrap()
+}
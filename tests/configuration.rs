@@ -1,5 +1,9 @@
+use embedded_hal::i2c::ErrorKind;
 use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
-use tmp1x2::{AlertPolarity as AP, ConversionRate as CR, FaultQueue as FQ, ThermostatMode as TM};
+use tmp1x2::{
+    AlertPolarity as AP, Configuration, ConfigurationDiff, ConversionRate as CR, FaultQueue as FQ,
+    Mismatch, ThermostatError, ThermostatMode as TM, ThermostatOutput, TransactionError,
+};
 
 mod common;
 use common::{
@@ -112,7 +116,7 @@ config_value_test!(
     can_set_cr_4,
     set_conversion_rate,
     CR::_4Hz,
-    DEFAULT_LSB | BFL::CONV_RATE1 & !BFL::CONV_RATE0,
+    (DEFAULT_LSB & !BFL::CONV_RATE0) | BFL::CONV_RATE1,
     DEFAULT_MSB
 );
 config_value_test!(
@@ -142,7 +146,7 @@ config_value_test!(
     set_fault_queue,
     FQ::_4,
     DEFAULT_LSB,
-    DEFAULT_MSB | BFH::FAULT_QUEUE1 & !BFH::FAULT_QUEUE0
+    (DEFAULT_MSB & !BFH::FAULT_QUEUE0) | BFH::FAULT_QUEUE1
 );
 config_value_test!(
     can_set_fq_6,
@@ -152,6 +156,44 @@ config_value_test!(
     DEFAULT_MSB | BFH::FAULT_QUEUE1 | BFH::FAULT_QUEUE0
 );
 
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_set_cr_4_from_8() {
+    let cr_8 = DEFAULT_LSB | BFL::CONV_RATE1 | BFL::CONV_RATE0;
+    let expectations = [
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONFIG, DEFAULT_MSB, cr_8]),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, cr_8 & !BFL::CONV_RATE0],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    dev.set_conversion_rate(CR::_8Hz).await.unwrap();
+    dev.set_conversion_rate(CR::_4Hz).await.unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_set_fq_4_from_6() {
+    let fq_6 = DEFAULT_MSB | BFH::FAULT_QUEUE1 | BFH::FAULT_QUEUE0;
+    let expectations = [
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONFIG, fq_6, DEFAULT_LSB]),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, fq_6 & !BFH::FAULT_QUEUE0, DEFAULT_LSB],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    dev.set_fault_queue(FQ::_6).await.unwrap();
+    dev.set_fault_queue(FQ::_4).await.unwrap();
+    dev.destroy().done();
+}
+
 config_value_test!(
     can_set_ap_low,
     set_alert_polarity,
@@ -255,3 +297,283 @@ async fn can_set_extended_high_temp_threshold() {
     dev.set_high_temperature_threshold(255.875).await.unwrap();
     dev.destroy().done();
 }
+
+fn get_configuration_read_expectations(config_msb: u8, t_high_msb: u8) -> [I2cTransaction; 3] {
+    [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG],
+            vec![config_msb, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_LOW], vec![0b0100_1011, 0]),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_HIGH], vec![t_high_msb, 0]),
+    ]
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn matching_configuration_has_no_differences() {
+    let expectations = get_configuration_read_expectations(DEFAULT_MSB, 0b0101_0000);
+    let mut dev = setup(&expectations);
+    let diff = dev
+        .compare_configuration(&Configuration::default())
+        .await
+        .unwrap();
+    assert!(diff.is_empty());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_compare_configuration() {
+    let expectations = get_configuration_read_expectations(
+        DEFAULT_MSB | BFH::FAULT_QUEUE1 | BFH::THERMOSTAT,
+        0b0101_0101,
+    );
+    let mut dev = setup(&expectations);
+    let diff = dev
+        .compare_configuration(&Configuration::default())
+        .await
+        .unwrap();
+    let expected = ConfigurationDiff {
+        fault_queue: Some(Mismatch {
+            expected: FQ::_1,
+            actual: FQ::_4,
+        }),
+        thermostat_mode: Some(Mismatch {
+            expected: TM::Comparator,
+            actual: TM::Interrupt,
+        }),
+        high_temperature_threshold: Some(Mismatch {
+            expected: 80.0,
+            actual: 85.0,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(expected, diff);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn reconcile_writes_only_differing_registers() {
+    let mut expectations = get_configuration_read_expectations(DEFAULT_MSB, 0b0101_0000).to_vec();
+    expectations.push(I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB | BFL::CONV_RATE0],
+    ));
+    expectations.push(I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::T_HIGH, 0b0101_1010, 0],
+    ));
+    let mut dev = setup(&expectations);
+    let desired = Configuration {
+        conversion_rate: CR::_8Hz,
+        high_temperature_threshold: 90.0,
+        ..Default::default()
+    };
+    let diff = dev.reconcile_configuration(&desired).await.unwrap();
+    assert_eq!(
+        Some(Mismatch {
+            expected: CR::_8Hz,
+            actual: CR::_4Hz
+        }),
+        diff.conversion_rate
+    );
+    assert!(diff.low_temperature_threshold.is_none());
+    dev.destroy().done();
+}
+
+fn get_configuration_write_expectations(
+    config_lsb: u8,
+    t_low: [u8; 2],
+    t_high: [u8; 2],
+) -> [I2cTransaction; 3] {
+    [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, config_lsb],
+        ),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_LOW, t_low[0], t_low[1]]),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_HIGH, t_high[0], t_high[1]]),
+    ]
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_apply_configuration() {
+    let mut expectations = get_configuration_read_expectations(DEFAULT_MSB, 0b0101_0000).to_vec();
+    expectations.extend(get_configuration_write_expectations(
+        DEFAULT_LSB | BFL::EXTENDED_MODE,
+        [0b0000_0000, 0b0000_0000],
+        [0b0100_1011, 0b0000_0000],
+    ));
+    let mut dev = setup(&expectations);
+    let configuration = Configuration {
+        extended_mode: true,
+        low_temperature_threshold: 0.0,
+        high_temperature_threshold: 150.0,
+        ..Default::default()
+    };
+    dev.apply_configuration(&configuration).await.unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn failed_configuration_is_rolled_back() {
+    let mut expectations = get_configuration_read_expectations(DEFAULT_MSB, 0b0101_0000).to_vec();
+    let mut apply = get_configuration_write_expectations(
+        DEFAULT_LSB | BFL::EXTENDED_MODE,
+        [0b0000_0000, 0b0000_0000],
+        [0b0100_1011, 0b0000_0000],
+    );
+    apply[2] = apply[2].clone().with_error(ErrorKind::Other);
+    expectations.extend(apply);
+    expectations.extend(get_configuration_write_expectations(
+        DEFAULT_LSB,
+        [0b0100_1011, 0],
+        [0b0101_0000, 0],
+    ));
+    let mut dev = setup(&expectations);
+    let configuration = Configuration {
+        extended_mode: true,
+        low_temperature_threshold: 0.0,
+        high_temperature_threshold: 150.0,
+        ..Default::default()
+    };
+    match dev.apply_configuration(&configuration).await {
+        Err(TransactionError::RolledBack(ErrorKind::Other)) => (),
+        _ => panic!(),
+    }
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn cached_config_is_read_back_after_failed_rollback() {
+    let mut expectations = get_configuration_read_expectations(DEFAULT_MSB, 0b0101_0000).to_vec();
+    let mut apply = get_configuration_write_expectations(
+        DEFAULT_LSB | BFL::EXTENDED_MODE,
+        [0b0000_0000, 0b0000_0000],
+        [0b0100_1011, 0b0000_0000],
+    );
+    apply[1] = apply[1].clone().with_error(ErrorKind::Other);
+    expectations.extend_from_slice(&apply[..2]);
+    expectations.push(
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+        )
+        .with_error(ErrorKind::Other),
+    );
+    expectations.push(I2cTransaction::write_read(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG],
+        vec![DEFAULT_MSB, DEFAULT_LSB | BFL::EXTENDED_MODE],
+    ));
+    expectations.push(I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![
+            Register::CONFIG,
+            DEFAULT_MSB | BFH::THERMOSTAT,
+            DEFAULT_LSB | BFL::EXTENDED_MODE,
+        ],
+    ));
+    let mut dev = setup(&expectations);
+    let configuration = Configuration {
+        extended_mode: true,
+        low_temperature_threshold: 0.0,
+        high_temperature_threshold: 150.0,
+        ..Default::default()
+    };
+    match dev.apply_configuration(&configuration).await {
+        Err(TransactionError::RollbackFailed {
+            error: ErrorKind::Other,
+            rollback_error: ErrorKind::Other,
+        }) => (),
+        _ => panic!(),
+    }
+    dev.set_thermostat_mode(TM::Interrupt).await.unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_configure_as_cooler_thermostat() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ALERT_POLARITY | BFH::FAULT_QUEUE1,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_LOW, 28, 0]),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_HIGH, 30, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    dev.configure_as_thermostat(30.0, 2.0, ThermostatOutput::Cooler, AP::ActiveHigh, FQ::_4)
+        .await
+        .unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_configure_as_heater_thermostat() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_LOW, 20, 0]),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_HIGH, 21, 0b1000_0000]),
+    ];
+    let mut dev = setup(&expectations);
+    dev.configure_as_thermostat(20.0, 1.5, ThermostatOutput::Heater, AP::ActiveHigh, FQ::_1)
+        .await
+        .unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn thermostat_window_must_be_representable() {
+    let mut dev = setup(&[]);
+    for (setpoint, hysteresis) in [(20.0, 0.0), (20.0, 0.05), (127.0, 1.0), (-128.5, 2.0)] {
+        match dev
+            .configure_as_thermostat(
+                setpoint,
+                hysteresis,
+                ThermostatOutput::Heater,
+                AP::ActiveLow,
+                FQ::_1,
+            )
+            .await
+        {
+            Err(ThermostatError::InvalidThresholds) => (),
+            _ => panic!(),
+        }
+    }
+    dev.destroy().done();
+}
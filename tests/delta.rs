@@ -0,0 +1,155 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::DeltaReporter;
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+fn temperature_read(msb: u8, lsb: u8) -> I2cTransaction {
+    I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![msb, lsb])
+}
+
+fn threshold_write(register: u8, msb: u8, lsb: u8) -> I2cTransaction {
+    I2cTransaction::write(DEVICE_ADDRESS, vec![register, msb, lsb])
+}
+
+fn config_write(msb: u8) -> I2cTransaction {
+    I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONFIG, msb, DEFAULT_LSB])
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_report_consecutive_rises() {
+    let expectations = [
+        temperature_read(25, 0),
+        threshold_write(Register::T_LOW, 24, 0b1000_0000),
+        threshold_write(Register::T_HIGH, 25, 0b1000_0000),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+        // First rise
+        temperature_read(26, 0b0100_0000),
+        threshold_write(Register::T_HIGH, 26, 0b1100_0000),
+        threshold_write(Register::T_LOW, 25, 0b1100_0000),
+        config_write(DEFAULT_MSB),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+        // Second rise
+        temperature_read(27, 0b1000_0000),
+        threshold_write(Register::T_HIGH, 28, 0),
+        threshold_write(Register::T_LOW, 27, 0),
+        config_write(DEFAULT_MSB),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+    ];
+    let mut dev = setup(&expectations);
+    let mut reporter = DeltaReporter::new(0.5).unwrap();
+    assert_eq!(None, reporter.center());
+    let temp = reporter.start(&mut dev).await.unwrap();
+    assert_eq!(Some(temp), reporter.center());
+    let temp = reporter.handle_alert(&mut dev).await.unwrap();
+    assert_eq!(Some(temp), reporter.center());
+    assert_eq!(Some(26.25), reporter.center());
+    let temp = reporter.handle_alert(&mut dev).await.unwrap();
+    assert_eq!(Some(temp), reporter.center());
+    assert_eq!(Some(27.5), reporter.center());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn lowers_low_threshold_first_on_fall() {
+    let expectations = [
+        temperature_read(25, 0),
+        threshold_write(Register::T_LOW, 24, 0b1000_0000),
+        threshold_write(Register::T_HIGH, 25, 0b1000_0000),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+        temperature_read(22, 0),
+        threshold_write(Register::T_LOW, 21, 0b1000_0000),
+        threshold_write(Register::T_HIGH, 22, 0b1000_0000),
+        config_write(DEFAULT_MSB),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+    ];
+    let mut dev = setup(&expectations);
+    let mut reporter = DeltaReporter::new(0.5).unwrap();
+    reporter.start(&mut dev).await.unwrap();
+    reporter.handle_alert(&mut dev).await.unwrap();
+    assert_eq!(Some(22.0), reporter.center());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn retries_restoring_interrupt_mode() {
+    let expectations = [
+        temperature_read(25, 0),
+        threshold_write(Register::T_LOW, 24, 0b1000_0000),
+        threshold_write(Register::T_HIGH, 25, 0b1000_0000),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+        temperature_read(26, 0),
+        threshold_write(Register::T_HIGH, 26, 0b1000_0000),
+        threshold_write(Register::T_LOW, 25, 0b1000_0000),
+        config_write(DEFAULT_MSB),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT).with_error(ErrorKind::Other),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+    ];
+    let mut dev = setup(&expectations);
+    let mut reporter = DeltaReporter::new(0.5).unwrap();
+    reporter.start(&mut dev).await.unwrap();
+    assert_eq!(26.0, reporter.handle_alert(&mut dev).await.unwrap());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn restores_window_if_it_cannot_be_written() {
+    let expectations = [
+        temperature_read(25, 0),
+        threshold_write(Register::T_LOW, 24, 0b1000_0000),
+        threshold_write(Register::T_HIGH, 25, 0b1000_0000),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+        temperature_read(26, 0),
+        threshold_write(Register::T_HIGH, 26, 0b1000_0000),
+        threshold_write(Register::T_LOW, 25, 0b1000_0000).with_error(ErrorKind::Other),
+        threshold_write(Register::T_HIGH, 25, 0b1000_0000),
+    ];
+    let mut dev = setup(&expectations);
+    let mut reporter = DeltaReporter::new(0.5).unwrap();
+    reporter.start(&mut dev).await.unwrap();
+    assert!(reporter.handle_alert(&mut dev).await.is_err());
+    assert_eq!(Some(25.0), reporter.center());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn window_is_limited_to_typed_range() {
+    let expectations = [
+        config_write(DEFAULT_MSB),
+        temperature_read(127, 0),
+        threshold_write(Register::T_LOW, 126, 0),
+        threshold_write(Register::T_HIGH, 127, 0b1111_0000),
+        config_write(DEFAULT_MSB | BFH::THERMOSTAT),
+    ];
+    let dev = setup(&expectations);
+    let mut dev = dev.into_normal().await.unwrap();
+    let mut reporter = DeltaReporter::new(1.0).unwrap();
+    reporter.start(&mut dev).await.unwrap();
+    dev.destroy().done();
+}
+
+#[test]
+fn rejects_non_positive_delta() {
+    assert_eq!(None, DeltaReporter::new(0.0));
+    assert_eq!(None, DeltaReporter::new(-1.0));
+    assert_eq!(None, DeltaReporter::new(f32::NAN));
+}
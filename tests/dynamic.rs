@@ -0,0 +1,139 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{ConversionMode, DynTmp1x2};
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_change_mode() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+        ),
+    ];
+    let mut dev = DynTmp1x2::from(setup(&expectations));
+    assert_eq!(ConversionMode::Continuous, dev.mode());
+    dev.set_mode(ConversionMode::Continuous).await.unwrap();
+    dev.set_mode(ConversionMode::OneShot).await.unwrap();
+    assert_eq!(ConversionMode::OneShot, dev.mode());
+    assert!(dev.as_one_shot_mut().is_some());
+    assert!(dev.as_continuous_mut().is_none());
+    dev.set_mode(ConversionMode::Continuous).await.unwrap();
+    assert_eq!(ConversionMode::Continuous, dev.mode());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn failed_mode_change_keeps_mode() {
+    let expectations = [I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+    )
+    .with_error(ErrorKind::Other)];
+    let mut dev = DynTmp1x2::from(setup(&expectations));
+    dev.set_mode(ConversionMode::OneShot)
+        .await
+        .expect_err("Should return an error");
+    assert_eq!(ConversionMode::Continuous, dev.mode());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_read_temperature_in_both_modes() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::read(
+            DEVICE_ADDRESS,
+            vec![DEFAULT_MSB | BFH::ONE_SHOT, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0011_0010, 0],
+        ),
+    ];
+    let mut dev = DynTmp1x2::from(setup(&expectations));
+    let temp = dev.read_temperature().await.unwrap();
+    assert_eq!(100.0, temp);
+    dev.set_mode(ConversionMode::OneShot).await.unwrap();
+    match dev.read_temperature().await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+    let temp = dev.read_temperature().await.unwrap();
+    assert_eq!(50.0, temp);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn keeps_driver_state_changed_through_accessor() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::read(
+            DEVICE_ADDRESS,
+            vec![DEFAULT_MSB | BFH::ONE_SHOT, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0011_0010, 0],
+        ),
+    ];
+    let mut dev = DynTmp1x2::from(setup(&expectations));
+    dev.set_mode(ConversionMode::OneShot).await.unwrap();
+    match dev.as_one_shot_mut().unwrap().read_temperature().await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+    let temp = dev.read_temperature().await.unwrap();
+    assert_eq!(50.0, temp);
+    dev.destroy().done();
+}
@@ -0,0 +1,56 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{Error, Filter, FilteredReading, MovingAverage};
+
+mod common;
+use common::{setup, Register, DEVICE_ADDRESS};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_read_filtered_temperature() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![20, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![21, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let mut filter = MovingAverage::<4>::new();
+    let reading = dev.read_temperature_filtered(&mut filter).await.unwrap();
+    assert_eq!(
+        FilteredReading {
+            raw: 20.0,
+            filtered: 20.0
+        },
+        reading
+    );
+    let reading = dev.read_temperature_filtered(&mut filter).await.unwrap();
+    assert_eq!(
+        FilteredReading {
+            raw: 21.0,
+            filtered: 20.5
+        },
+        reading
+    );
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn read_error_does_not_update_filter() {
+    let expectations =
+        [
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![20, 0])
+                .with_error(ErrorKind::Other),
+        ];
+    let mut dev = setup(&expectations);
+    let mut filter = MovingAverage::<4>::new();
+    match dev.read_temperature_filtered(&mut filter).await {
+        Err(Error::I2C(_)) => (),
+        _ => panic!(),
+    }
+    assert_eq!(30.0, filter.update(30.0));
+    dev.destroy().done();
+}
@@ -0,0 +1,163 @@
+use core::cell::Cell;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{
+    AlertPolarity, Configuration, Error, FaultQueue, GuardSettings, OverTemperatureGuard,
+    ShutdownReason,
+};
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, BitFlagsLow as BFL, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+const SETTINGS: GuardSettings = GuardSettings {
+    critical_limit: 80.0,
+    hysteresis: 5.0,
+    alert_polarity: AlertPolarity::ActiveLow,
+    fault_queue: FaultQueue::_2,
+    max_failed_reads: 2,
+};
+
+fn temperature_read(msb: u8) -> I2cTransaction {
+    I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![msb, 0])
+}
+
+fn config_read(alert: bool) -> I2cTransaction {
+    let lsb = if alert {
+        DEFAULT_LSB & !BFL::ALERT
+    } else {
+        DEFAULT_LSB
+    };
+    I2cTransaction::write_read(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG],
+        vec![DEFAULT_MSB, lsb],
+    )
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_arm() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::FAULT_QUEUE0,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_LOW, 75, 0]),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_HIGH, 80, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let mut guard = OverTemperatureGuard::new(SETTINGS, |_| panic!());
+    guard.arm(&mut dev).await.unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn shuts_down_once_on_over_temperature() {
+    let expectations = [
+        temperature_read(79),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![80, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let calls = Cell::new(0);
+    let mut guard = OverTemperatureGuard::new(SETTINGS, |reason| {
+        assert_eq!(
+            ShutdownReason::OverTemperature { temperature: 80.0 },
+            reason
+        );
+        calls.set(calls.get() + 1);
+    });
+    let temperature = guard.check(&mut dev).await.unwrap();
+    assert_eq!(79.0, temperature);
+    assert_eq!(None, guard.tripped());
+    guard.check(&mut dev).await.unwrap();
+    assert_eq!(1, calls.get());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn shuts_down_on_hardware_alert_if_config_unknown() {
+    let config_write = I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+    )
+    .with_error(ErrorKind::Other);
+    let expectations = [
+        config_read(false),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_LOW], vec![75, 0]),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_HIGH], vec![80, 0]),
+        config_write.clone(),
+        config_write,
+        temperature_read(70),
+        config_read(true),
+    ];
+    let mut dev = setup(&expectations);
+    let shutdown = Cell::new(None);
+    let mut guard = OverTemperatureGuard::new(SETTINGS, |reason| shutdown.set(Some(reason)));
+    assert!(dev
+        .apply_configuration(&Configuration::default())
+        .await
+        .is_err());
+    guard.check(&mut dev).await.unwrap();
+    assert_eq!(Some(ShutdownReason::HardwareAlert), shutdown.get());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn shuts_down_after_consecutive_failed_reads() {
+    let expectations = [
+        temperature_read(70).with_error(ErrorKind::Other),
+        temperature_read(70),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![70, 0]).with_error(ErrorKind::Other),
+        temperature_read(70).with_error(ErrorKind::Other),
+    ];
+    let mut dev = setup(&expectations);
+    let shutdown = Cell::new(None);
+    let mut guard = OverTemperatureGuard::new(SETTINGS, |reason| shutdown.set(Some(reason)));
+    match guard.check(&mut dev).await {
+        Err(Error::I2C(_)) => (),
+        _ => panic!(),
+    }
+    guard.check(&mut dev).await.unwrap();
+    guard.check(&mut dev).await.unwrap_err();
+    assert_eq!(None, shutdown.get());
+    guard.check(&mut dev).await.unwrap_err();
+    assert_eq!(Some(ShutdownReason::SensorFailure), shutdown.get());
+    assert_eq!(Some(ShutdownReason::SensorFailure), guard.tripped());
+    dev.destroy().done();
+}
+
+#[test]
+fn shuts_down_on_active_alert_pin() {
+    let mut pin = PinMock::new(&[
+        PinTransaction::get(State::High),
+        PinTransaction::get(State::Low),
+    ]);
+    let shutdown = Cell::new(None);
+    let mut guard = OverTemperatureGuard::new(SETTINGS, |reason| shutdown.set(Some(reason)));
+    assert!(!guard.check_alert_pin(&mut pin).unwrap());
+    assert_eq!(None, shutdown.get());
+    assert!(guard.check_alert_pin(&mut pin).unwrap());
+    assert_eq!(Some(ShutdownReason::HardwareAlert), shutdown.get());
+    guard.reset();
+    assert_eq!(None, guard.tripped());
+    pin.done();
+}
@@ -0,0 +1,110 @@
+use core::cell::Cell;
+use core::time::Duration;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use embedded_hal_mock::eh1::MockError;
+use std::io;
+use tmp1x2::{ControlError, Error, OnOffController, OnOffSettings, ThermostatOutput};
+
+mod common;
+use common::{setup, Register, DEVICE_ADDRESS};
+
+fn settings() -> OnOffSettings {
+    OnOffSettings {
+        setpoint: 20.0,
+        hysteresis: 1.0,
+        output: ThermostatOutput::Heater,
+        min_on_time: Duration::from_secs(10),
+        min_off_time: Duration::from_secs(10),
+        failsafe_on: false,
+    }
+}
+
+fn temperature_read(msb: u8, lsb: u8) -> I2cTransaction {
+    I2cTransaction::read(DEVICE_ADDRESS, vec![msb, lsb])
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_switch_heater_with_minimum_times() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![19, 0]),
+        temperature_read(22, 0),
+        temperature_read(22, 0),
+        temperature_read(19, 0),
+        temperature_read(19, 0),
+    ];
+    let mut dev = setup(&expectations);
+    let pin = PinMock::new(&[
+        PinTransaction::set(State::High),
+        PinTransaction::set(State::Low),
+        PinTransaction::set(State::High),
+    ]);
+    let now = Cell::new(Duration::ZERO);
+    let mut controller = OnOffController::new(pin, || now.get(), settings());
+    assert_eq!(None, controller.is_on());
+    assert!(controller.update(&mut dev).await.unwrap());
+    now.set(Duration::from_secs(5));
+    assert!(controller.update(&mut dev).await.unwrap());
+    now.set(Duration::from_secs(10));
+    assert!(!controller.update(&mut dev).await.unwrap());
+    now.set(Duration::from_secs(15));
+    assert!(!controller.update(&mut dev).await.unwrap());
+    now.set(Duration::from_secs(20));
+    assert!(controller.update(&mut dev).await.unwrap());
+    assert_eq!(Some(true), controller.is_on());
+    dev.destroy().done();
+    controller.destroy().0.done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn sets_failsafe_state_on_sensor_error() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![19, 0]),
+        temperature_read(19, 0).with_error(ErrorKind::Other),
+    ];
+    let mut dev = setup(&expectations);
+    let pin = PinMock::new(&[
+        PinTransaction::set(State::High),
+        PinTransaction::set(State::Low),
+    ]);
+    let mut controller = OnOffController::new(pin, || Duration::ZERO, settings());
+    assert!(controller.update(&mut dev).await.unwrap());
+    match controller.update(&mut dev).await {
+        Err(ControlError::Sensor(Error::I2C(_))) => (),
+        _ => panic!(),
+    }
+    assert_eq!(Some(false), controller.is_on());
+    dev.destroy().done();
+    controller.destroy().0.done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn returns_output_error() {
+    let expectations = [I2cTransaction::write_read(
+        DEVICE_ADDRESS,
+        vec![Register::TEMPERATURE],
+        vec![19, 0],
+    )];
+    let mut dev = setup(&expectations);
+    let pin = PinMock::new(&[
+        PinTransaction::set(State::High).with_error(MockError::Io(io::ErrorKind::NotConnected))
+    ]);
+    let mut controller = OnOffController::new(pin, || Duration::ZERO, settings());
+    match controller.update(&mut dev).await {
+        Err(ControlError::Output(_)) => (),
+        _ => panic!(),
+    }
+    assert_eq!(None, controller.is_on());
+    dev.destroy().done();
+    controller.destroy().0.done();
+}
@@ -0,0 +1,138 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use embedded_hal_mock::eh1::pwm::{Mock as PwmMock, Transaction as PwmTransaction};
+use tmp1x2::{ControlError, Error, PidController, PidSettings, ThermostatOutput};
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, BitFlagsLow as BFL, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+macro_rules! assert_near {
+    ($left:expr, $right:expr) => {
+        assert!(f32::abs($left - $right) < 1e-5);
+    };
+}
+
+fn settings() -> PidSettings {
+    PidSettings {
+        setpoint: 20.0,
+        kp: 0.1,
+        ki: 0.01,
+        kd: 1.0,
+        output: ThermostatOutput::Heater,
+        output_min: 0.0,
+        output_max: 1.0,
+        failsafe_output: 0.5,
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn uses_conversion_period_as_time_step() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB,
+                DEFAULT_LSB & !BFL::CONV_RATE1 | BFL::CONV_RATE0,
+            ],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![18, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![18, 0b1000_0000]),
+    ];
+    let mut dev = setup(&expectations);
+    dev.set_conversion_rate(tmp1x2::ConversionRate::_1Hz)
+        .await
+        .unwrap();
+    let pwm = PwmMock::new(&[
+        PwmTransaction::max_duty_cycle(1000),
+        PwmTransaction::set_duty_cycle(220),
+        PwmTransaction::max_duty_cycle(1000),
+        PwmTransaction::set_duty_cycle(0),
+    ]);
+    let mut controller = PidController::new(pwm, settings());
+    // P = 0.2, I = 0.02
+    let output = controller.update(&mut dev).await.unwrap();
+    assert_near!(0.22, output);
+    // P = 0.15, I = 0.035, D = -0.5
+    let output = controller.update(&mut dev).await.unwrap();
+    assert_near!(0.0, output);
+    dev.destroy().done();
+    controller.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_update_from_one_shot_measurements() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+                DEFAULT_LSB,
+            ],
+        ),
+        I2cTransaction::read(
+            DEVICE_ADDRESS,
+            vec![DEFAULT_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![18, 0]),
+    ];
+    let pwm = PwmMock::new(&[
+        PwmTransaction::max_duty_cycle(100),
+        PwmTransaction::set_duty_cycle(40),
+    ]);
+    let mut dev = setup(&expectations).into_one_shot().await.unwrap();
+    let mut controller = PidController::new(pwm, settings());
+    let interval = core::time::Duration::from_secs(10);
+    match controller.update_one_shot(&mut dev, interval).await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+    // P = 0.2, I = 0.2
+    let output = controller
+        .update_one_shot(&mut dev, interval)
+        .await
+        .unwrap();
+    assert_near!(0.4, output);
+    assert_eq!(Some(output), controller.output());
+    dev.destroy().done();
+    controller.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn sets_failsafe_output_on_sensor_error() {
+    let expectations =
+        [
+            I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![18, 0])
+                .with_error(ErrorKind::Other),
+        ];
+    let pwm = PwmMock::new(&[
+        PwmTransaction::max_duty_cycle(1000),
+        PwmTransaction::set_duty_cycle(500),
+    ]);
+    let mut dev = setup(&expectations);
+    let mut controller = PidController::new(pwm, settings());
+    match controller.update(&mut dev).await {
+        Err(ControlError::Sensor(Error::I2C(_))) => (),
+        _ => panic!(),
+    }
+    assert_eq!(Some(0.5), controller.output());
+    dev.destroy().done();
+    controller.destroy().done();
+}
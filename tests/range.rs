@@ -0,0 +1,126 @@
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{ExtendedThreshold, ModeChangeError, NormalThreshold};
+
+mod common;
+use common::{
+    setup, BitFlagsLow as BFL, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+const HIGH: NormalThreshold = NormalThreshold::from_celsius(100);
+const EXTENDED_HIGH: ExtendedThreshold = ExtendedThreshold::from_celsius(200);
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_set_normal_thresholds() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_HIGH, 0b0110_0100, 0]),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::T_LOW, 0b1110_0111, 0b1000_0000],
+        ),
+    ];
+    let dev = setup(&expectations);
+    let mut dev = dev.into_normal().await.unwrap();
+    dev.set_high_temperature_threshold(HIGH).await.unwrap();
+    dev.set_low_temperature_threshold(NormalThreshold::new(-24.5).unwrap())
+        .await
+        .unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_set_extended_thresholds() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB,
+                DEFAULT_LSB | BFL::EXTENDED_MODE,
+            ],
+        ),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_HIGH, 0b0110_0100, 0]),
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::T_LOW, 0b0011_0010, 0]),
+    ];
+    let dev = setup(&expectations);
+    let mut dev = dev.into_extended().await.unwrap();
+    dev.set_high_temperature_threshold(EXTENDED_HIGH)
+        .await
+        .unwrap();
+    dev.set_low_temperature_threshold(NormalThreshold::from_celsius(100).into())
+        .await
+        .unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_change_back_into_normal_and_dynamic_range() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB,
+                DEFAULT_LSB | BFL::EXTENDED_MODE,
+            ],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::T_HIGH, 0b0111_1111, 0b1111_0000],
+        ),
+    ];
+    let dev = setup(&expectations);
+    let dev = dev.into_extended().await.unwrap();
+    let dev = dev.into_normal().await.unwrap();
+    let mut dev = dev.into_dynamic_range();
+    dev.set_high_temperature_threshold(200.0).await.unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn range_change_error_returns_unchanged_device() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB,
+                DEFAULT_LSB | BFL::EXTENDED_MODE,
+            ],
+        )
+        .with_error(ErrorKind::Other),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+        ),
+    ];
+    let dev = setup(&expectations);
+    let mut dev = match dev.into_extended().await {
+        Err(ModeChangeError::I2C(ErrorKind::Other, dev)) => dev,
+        _ => panic!("expected a mode change error"),
+    };
+    // The cached configuration must be unchanged.
+    dev.disable_extended_mode().await.unwrap();
+    dev.destroy().done();
+}
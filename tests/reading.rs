@@ -1,3 +1,6 @@
+#![allow(clippy::float_equality_without_abs, clippy::legacy_numeric_constants)]
+
+use embedded_hal::i2c::ErrorKind;
 use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
 
 mod common;
@@ -124,11 +127,7 @@ async fn in_one_shot_read_temperature_returns_would_block_if_not_ready() {
                 DEFAULT_CONFIG_LSB,
             ],
         ),
-        I2cTransaction::write_read(
-            DEVICE_ADDRESS,
-            vec![Register::CONFIG],
-            vec![DEFAULT_CONFIG_MSB, DEFAULT_CONFIG_LSB],
-        ),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![DEFAULT_CONFIG_MSB, DEFAULT_CONFIG_LSB]),
     ];
     let dev = setup(&expectations);
     let mut dev = dev.into_one_shot().await.unwrap();
@@ -164,9 +163,8 @@ async fn in_one_shot_can_read_temperature() {
                 DEFAULT_CONFIG_LSB,
             ],
         ),
-        I2cTransaction::write_read(
+        I2cTransaction::read(
             DEVICE_ADDRESS,
-            vec![Register::CONFIG],
             vec![DEFAULT_CONFIG_MSB | BFH::ONE_SHOT, DEFAULT_CONFIG_LSB],
         ),
         I2cTransaction::write_read(
@@ -200,3 +198,177 @@ async fn in_continuous_can_read_temperature() {
     assert_near!(100.0, value);
     dev.destroy().done();
 }
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn repeated_temperature_reads_do_not_resend_pointer() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![0b0011_0010, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let temp = dev.read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    let temp = dev.read_temperature().await.unwrap();
+    assert_near!(50.0, temp);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn register_write_moves_pointer() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_CONFIG_MSB,
+                DEFAULT_CONFIG_LSB | BFL::EXTENDED_MODE,
+            ],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0011_0010, 1],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    let temp = dev.read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    dev.enable_extended_mode().await.unwrap();
+    let temp = dev.read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn pointer_is_resent_after_i2c_error() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![0, 0]).with_error(ErrorKind::Other),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    let temp = dev.read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    dev.read_temperature()
+        .await
+        .expect_err("Should return an error");
+    let temp = dev.read_temperature().await.unwrap();
+    assert_near!(100.0, temp);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn in_continuous_can_read_temperature_coarse() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b1110_0111],
+        ),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![0b0110_0100]),
+    ];
+    let mut dev = setup(&expectations);
+    let value = dev.read_temperature_coarse().await.unwrap();
+    assert_eq!(-25, value);
+    let value = dev.read_temperature_coarse().await.unwrap();
+    assert_eq!(100, value);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn in_continuous_can_read_temperature_coarse_extended_mode() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_CONFIG_MSB,
+                DEFAULT_CONFIG_LSB | BFL::EXTENDED_MODE,
+            ],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0100_1011],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    dev.enable_extended_mode().await.unwrap();
+    let value = dev.read_temperature_coarse().await.unwrap();
+    assert_eq!(150, value);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn in_one_shot_can_read_temperature_coarse() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_CONFIG_MSB | BFH::SHUTDOWN,
+                DEFAULT_CONFIG_LSB,
+            ],
+        ),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_CONFIG_MSB | BFH::ONE_SHOT | BFH::SHUTDOWN,
+                DEFAULT_CONFIG_LSB,
+            ],
+        ),
+        I2cTransaction::read(
+            DEVICE_ADDRESS,
+            vec![DEFAULT_CONFIG_MSB | BFH::ONE_SHOT, DEFAULT_CONFIG_LSB],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100],
+        ),
+    ];
+    let dev = setup(&expectations);
+    let mut dev = dev.into_one_shot().await.unwrap();
+    dev.read_temperature_coarse()
+        .await
+        .expect_err("Should return an error");
+    let temp = dev.read_temperature_coarse().await.unwrap();
+    assert_eq!(100, temp);
+    dev.destroy().done();
+}
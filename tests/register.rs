@@ -0,0 +1,145 @@
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{
+    AlertPolarity, ConfigRegister, ConversionRate, FaultQueue, Register as Reg, RegisterDump,
+    TemperatureRegister, ThermostatMode, WritableRegister,
+};
+
+mod common;
+use common::{
+    setup, BitFlagsHigh as BFH, BitFlagsLow as BFL, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_read_raw_register() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG],
+            vec![DEFAULT_MSB | BFH::THERMOSTAT, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0110_0100, 0],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    let config = dev.read_register_raw(Reg::Config).await.unwrap();
+    let config = ConfigRegister::from_bits(config);
+    assert_eq!(ThermostatMode::Interrupt, config.thermostat_mode());
+    let temp = dev.read_register_raw(Reg::Temperature).await.unwrap();
+    assert_eq!(0b0110_0100_0000_0000, temp);
+    assert_eq!(100.0, TemperatureRegister::from_bits(temp).celsius());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_write_raw_register() {
+    let expectations = [I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::T_HIGH, 0b0101_0000, 0b0001_0000],
+    )];
+    let mut dev = setup(&expectations);
+    dev.write_register_raw(WritableRegister::THigh, 0b0101_0000_0001_0000)
+        .await
+        .unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn raw_config_write_updates_cached_config() {
+    let raw_msb = DEFAULT_MSB | BFH::ALERT_POLARITY | BFH::ONE_SHOT;
+    let expectations = [
+        I2cTransaction::write(DEVICE_ADDRESS, vec![Register::CONFIG, raw_msb, DEFAULT_LSB]),
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB | BFH::ALERT_POLARITY,
+                DEFAULT_LSB | BFL::EXTENDED_MODE,
+            ],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    dev.write_register_raw(
+        WritableRegister::Config,
+        u16::from(raw_msb) << 8 | u16::from(DEFAULT_LSB),
+    )
+    .await
+    .unwrap();
+    dev.enable_extended_mode().await.unwrap();
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_dump_registers() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::TEMPERATURE],
+            vec![0b0001_1001, 0b1000_0001],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG],
+            vec![
+                DEFAULT_MSB | BFH::FAULT_QUEUE1 | BFH::ALERT_POLARITY,
+                BFL::CONV_RATE0 | BFL::EXTENDED_MODE,
+            ],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::T_LOW],
+            vec![0b1111_0011, 0b1000_0000],
+        ),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::T_HIGH],
+            vec![0b0100_1011, 0b0000_0000],
+        ),
+    ];
+    let mut dev = setup(&expectations);
+    let dump = dev.dump().await.unwrap();
+    let expected = RegisterDump {
+        temperature: 51.0,
+        is_shutdown: false,
+        one_shot_bit: false,
+        thermostat_mode: ThermostatMode::Comparator,
+        alert_polarity: AlertPolarity::ActiveHigh,
+        fault_queue: FaultQueue::_4,
+        resolution_bits: 0b11,
+        is_extended_mode: true,
+        alert_bit: false,
+        conversion_rate: ConversionRate::_1Hz,
+        low_temperature_threshold: -25.0,
+        high_temperature_threshold: 150.0,
+    };
+    assert_eq!(expected, dump);
+    let report = "Temperature: 51.0000°C
+Shutdown mode: disabled
+One-shot bit: 0
+Thermostat mode: comparator
+Alert polarity: active high
+Fault queue: 4 consecutive fault(s)
+Resolution bits: 0b11
+Extended mode: enabled
+Alert bit: 0
+Conversion rate: 1Hz
+Low temperature threshold: -25.0000°C
+High temperature threshold: 150.0000°C";
+    assert_eq!(report, format!("{}", dump));
+    dev.destroy().done();
+}
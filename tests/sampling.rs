@@ -0,0 +1,122 @@
+use core::cell::Cell;
+use core::time::Duration;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{Configuration, ConversionRate, FreshSampleTracker};
+
+mod common;
+use common::{
+    setup, BitFlagsLow as BFL, Register, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+fn temperature_read(msb: u8) -> I2cTransaction {
+    I2cTransaction::read(DEVICE_ADDRESS, vec![msb, 0])
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn returns_only_fresh_samples() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![25, 0]),
+        temperature_read(26),
+    ];
+    let now = Cell::new(Duration::from_millis(1000));
+    let mut tracker = FreshSampleTracker::new(|| now.get());
+    let mut dev = setup(&expectations);
+    assert_eq!(Some(true), tracker.is_new_sample_available(&dev));
+    let temp = tracker.read_fresh_temperature(&mut dev).await.unwrap();
+    assert_eq!(25, temp as i16);
+
+    // The default conversion rate is 4Hz.
+    now.set(Duration::from_millis(1249));
+    assert_eq!(Some(false), tracker.is_new_sample_available(&dev));
+    match tracker.read_fresh_temperature(&mut dev).await {
+        Err(nb::Error::WouldBlock) => (),
+        _ => panic!(),
+    }
+
+    now.set(Duration::from_millis(1250));
+    assert_eq!(Some(true), tracker.is_new_sample_available(&dev));
+    let temp = tracker.read_fresh_temperature(&mut dev).await.unwrap();
+    assert_eq!(26, temp as i16);
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn uses_cached_conversion_rate() {
+    let expectations = [
+        I2cTransaction::write(
+            DEVICE_ADDRESS,
+            vec![
+                Register::CONFIG,
+                DEFAULT_MSB,
+                DEFAULT_LSB & !BFL::CONV_RATE1 | BFL::CONV_RATE0,
+            ],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![25, 0]),
+    ];
+    let now = Cell::new(Duration::ZERO);
+    let mut tracker = FreshSampleTracker::new(|| now.get());
+    let mut dev = setup(&expectations);
+    dev.set_conversion_rate(ConversionRate::_1Hz).await.unwrap();
+    tracker.read_fresh_temperature(&mut dev).await.unwrap();
+    now.set(Duration::from_millis(999));
+    assert_eq!(Some(false), tracker.is_new_sample_available(&dev));
+    now.set(Duration::from_secs(1));
+    assert_eq!(Some(true), tracker.is_new_sample_available(&dev));
+    tracker.reset();
+    now.set(Duration::from_millis(1001));
+    assert_eq!(Some(true), tracker.is_new_sample_available(&dev));
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn reads_back_unknown_conversion_rate() {
+    let config_write = I2cTransaction::write(
+        DEVICE_ADDRESS,
+        vec![Register::CONFIG, DEFAULT_MSB, DEFAULT_LSB],
+    )
+    .with_error(ErrorKind::Other);
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG],
+            vec![DEFAULT_MSB, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_LOW], vec![75, 0]),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_HIGH], vec![80, 0]),
+        config_write.clone(),
+        config_write,
+        // The rate was changed to 1Hz by someone else.
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG],
+            vec![
+                DEFAULT_MSB,
+                DEFAULT_LSB & !BFL::CONV_RATE1 | BFL::CONV_RATE0,
+            ],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![25, 0]),
+    ];
+    let now = Cell::new(Duration::ZERO);
+    let mut tracker = FreshSampleTracker::new(|| now.get());
+    let mut dev = setup(&expectations);
+    assert!(dev
+        .apply_configuration(&Configuration::default())
+        .await
+        .is_err());
+    assert_eq!(None, tracker.is_new_sample_available(&dev));
+    tracker.read_fresh_temperature(&mut dev).await.unwrap();
+    now.set(Duration::from_millis(999));
+    assert_eq!(Some(false), tracker.is_new_sample_available(&dev));
+    dev.destroy().done();
+}
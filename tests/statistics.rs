@@ -0,0 +1,70 @@
+use core::time::Duration;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::StatisticsTracker;
+
+mod common;
+use common::{
+    read_at, setup, Register, TestClock, DEFAULT_CONFIG_LSB as DEFAULT_LSB,
+    DEFAULT_CONFIG_MSB as DEFAULT_MSB, DEVICE_ADDRESS,
+};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_track_readings() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![20, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![45, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![25, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let clock = TestClock::default();
+    let mut tracker = StatisticsTracker::new(|| clock.now(), 0.0, 40.0);
+    for seconds in [0, 10, 30] {
+        read_at(&mut dev, &mut tracker, &clock, seconds).await;
+    }
+    let snapshot = tracker.take_snapshot().unwrap();
+    assert_eq!(3, snapshot.count);
+    assert_eq!(30.0, snapshot.mean);
+    assert_eq!(20.0, snapshot.min);
+    assert_eq!(Duration::from_secs(10), snapshot.max_time);
+    assert_eq!(Duration::from_secs(20), snapshot.time_above_high);
+    assert_eq!(Duration::ZERO, snapshot.time_below_low);
+    assert_eq!(None, tracker.snapshot());
+    dev.destroy().done();
+}
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_use_device_thresholds() {
+    let expectations = [
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::CONFIG],
+            vec![DEFAULT_MSB, DEFAULT_LSB],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::T_LOW], vec![10, 0]),
+        I2cTransaction::write_read(
+            DEVICE_ADDRESS,
+            vec![Register::T_HIGH],
+            vec![30, 0b1000_0000],
+        ),
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![31, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![30, 0b1000_0000]),
+    ];
+    let mut dev = setup(&expectations);
+    let clock = TestClock::default();
+    let mut tracker = StatisticsTracker::with_device_thresholds(|| clock.now(), &mut dev)
+        .await
+        .unwrap();
+    read_at(&mut dev, &mut tracker, &clock, 0).await;
+    read_at(&mut dev, &mut tracker, &clock, 5).await;
+    clock.set(15);
+    let snapshot = tracker.snapshot().unwrap();
+    // 30.5°C is not above the high threshold
+    assert_eq!(Duration::from_secs(5), snapshot.time_above_high);
+    dev.destroy().done();
+}
@@ -0,0 +1,30 @@
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::ThermalModel;
+
+mod common;
+use common::{read_at, setup, Register, TestClock, DEVICE_ADDRESS};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_predict_steady_state_from_readings() {
+    // The remaining difference to 40°C halves every 10 seconds.
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![24, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![32, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![36, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![38, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![39, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let clock = TestClock::default();
+    let mut model = ThermalModel::new(|| clock.now(), 1.0);
+    for i in 0..5 {
+        read_at(&mut dev, &mut model, &clock, i * 10).await;
+    }
+    let prediction = model.prediction().unwrap();
+    assert!(f32::abs(prediction.temperature - 40.0) < 1e-3);
+    assert!(prediction.confidence > 0.999);
+    dev.destroy().done();
+}
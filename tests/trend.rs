@@ -0,0 +1,35 @@
+use core::time::Duration;
+use embedded_hal_mock::eh1::i2c::Transaction as I2cTransaction;
+use tmp1x2::{Trend, TrendEstimator, TrendSettings};
+
+mod common;
+use common::{read_at, setup, Register, TestClock, DEVICE_ADDRESS};
+
+#[maybe_async_cfg::maybe(
+    sync(cfg(not(feature = "async")), test),
+    async(feature = "async", tokio::test)
+)]
+async fn can_estimate_trend_from_readings() {
+    let expectations = [
+        I2cTransaction::write_read(DEVICE_ADDRESS, vec![Register::TEMPERATURE], vec![20, 0]),
+        I2cTransaction::read(DEVICE_ADDRESS, vec![22, 0]),
+    ];
+    let mut dev = setup(&expectations);
+    let clock = TestClock::default();
+    let settings = TrendSettings {
+        rising_rate: 0.5,
+        falling_rate: 0.5,
+    };
+    let mut estimator = TrendEstimator::<_, 4>::new(|| clock.now(), settings);
+    read_at(&mut dev, &mut estimator, &clock, 0).await;
+    assert_eq!(None, estimator.trend());
+    let temperature = read_at(&mut dev, &mut estimator, &clock, 2).await;
+    assert_eq!(22.0, temperature);
+    assert_eq!(Some(1.0), estimator.slope());
+    assert_eq!(Some(Trend::RisingFast), estimator.trend());
+    assert_eq!(
+        Some(Duration::from_secs(8)),
+        estimator.time_to_threshold(30.0)
+    );
+    dev.destroy().done();
+}